@@ -1,4 +1,4 @@
-use std::env::args;
+use std::{env::args, process};
 
 use rscheck::spell_checker::{
     options::{show_manual, Options},
@@ -7,11 +7,11 @@ use rscheck::spell_checker::{
 
 fn main() {
     match Options::create(args()) {
-        Ok(options) => {
-            if spell_check(&options).is_err() {
-                println!("Unknown error occurred while checking");
-            }
-        }
+        Ok(options) => match spell_check(&options) {
+            Ok(stats) if stats.problems() > 0 => process::exit(1),
+            Ok(_) => {}
+            Err(_) => println!("Unknown error occurred while checking"),
+        },
         Err(error) => {
             show_manual();
             println!("\n\n{error}");
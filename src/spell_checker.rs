@@ -1,71 +1,97 @@
+pub mod builtin;
+pub mod cache;
+pub mod commit_message;
+pub mod dict;
+pub mod dictionary;
+pub mod encoding;
 pub mod file_utils;
+pub mod fix;
+pub mod git_diff;
+pub mod hook;
+pub mod learn;
+pub mod lsp;
+pub mod non_words;
 pub mod options;
 pub mod parse_variables;
+pub mod render;
+pub mod repeated_words;
+pub mod report;
 pub mod stats;
+pub mod style;
+pub mod tokenizer;
+pub mod typo;
+pub mod variants;
+pub mod watch;
+pub mod wordlist;
 
 use crate::trie::Trie;
-use file_utils::get_files;
-use io::BufReader;
-use std::fs::{canonicalize, File};
-use std::io::{self, BufRead, ErrorKind};
-use std::path::{Path, PathBuf};
+use dictionary::{Dictionaries, Dictionary, Lookup, DEFAULT_DICTIONARY};
+use encoding::{decode, select_encoding};
+use file_utils::{get_files, is_binary};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, canonicalize};
+use std::hash::Hasher;
+use std::io::{self, ErrorKind, Read};
+use std::ops::Range;
+use std::path::Path;
 use std::process;
 
-use self::file_utils::{get_words_from_line, read_lines};
-use self::options::Options;
+use self::cache::{is_cache_path, CachedResult, Fnv, ResultCache};
+use self::commit_message::clean_commit_message;
+use self::git_diff::ChangedLines;
+use self::lsp::PROJECT_WORDLIST;
+use self::non_words::NonWordFilter;
+use self::options::{Options, STDIN_TARGET};
+use self::render::Renderer;
+use self::repeated_words::RepeatedWords;
+use self::report::{note, prints_files, show_result};
 use self::stats::CheckStats;
-
-fn feed_trie(
-    path: &Path,
-    ignore_list: &Option<&mut Trie>,
-    trie: &mut Trie,
+use self::style::{match_case, StyleRules};
+use self::tokenizer::{
+    get_words_from_line, normalize_apostrophes, split_compound, split_identifier, Token,
+};
+use self::typo::{Fix, Position, Typo, TypoKind};
+use self::variants::{detect_variant, Variant, VariantMode, Variants};
+use self::wordlist::{parse_wordlist, Entry};
+
+fn filter_wordlist(
+    entries: Vec<Entry>,
+    ignore_list: Option<&Trie>,
     options: &Options,
-) -> io::Result<()> {
-    let lines = read_lines(path)?;
+) -> Vec<Entry> {
+    let mut words = Vec::new();
 
     match ignore_list {
         Some(ignore_list) => {
-            for line in lines.map_while(Result::ok) {
-                if line.len() < options.min.into()
-                    || line.len() > options.max.into()
-                    || ignore_list.search(&line)
+            for entry in entries {
+                let len = entry.word.chars().count();
+                if len < options.min.into()
+                    || len > options.max.into()
+                    || ignore_list.search(&entry.word.to_lowercase())
                 {
                     continue;
                 }
 
-                trie.insert(&line);
+                words.push(entry);
             }
         }
         None => {
-            for line in lines.map_while(Result::ok) {
-                if line.len() >= 3 {
-                    trie.insert(&line);
+            for entry in entries {
+                if entry.word.chars().count() >= 3 {
+                    words.push(Entry::new(&entry.word.to_lowercase()));
                 }
             }
         }
     }
 
-    Ok(())
-}
-
-fn check_word(
-    word: &str,
-    trie: &Trie,
-    ignore_list: &Trie,
-    counter: &mut u64,
-    options: &Options,
-    line_num: &usize,
-) {
-    if !trie.search(word) && is_word_correct(word, ignore_list, options) {
-        println!("  * {}:{}", word, line_num);
-        *counter += 1;
-    }
+    words
 }
 
 fn is_word_correct(word: &str, ignore_list: &Trie, options: &Options) -> bool {
-    if word.len() < options.min.into()
-        || word.len() > options.max.into()
-        || ignore_list.search(word)
+    let len = word.chars().count();
+    if len < options.min.into()
+        || len > options.max.into()
+        || ignore_list.search(&word.to_lowercase())
     {
         return false;
     }
@@ -73,119 +99,613 @@ fn is_word_correct(word: &str, ignore_list: &Trie, options: &Options) -> bool {
     true
 }
 
-fn check_correctness(
+const MAX_SUGGESTIONS: usize = 3;
+
+// typos of already checked lines by line text
+pub type LineCache = HashMap<String, Vec<Typo>>;
+
+fn hash_options(options: &Options, hasher: &mut Fnv) {
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.write_u16(options.min);
+    hasher.write_u16(options.max);
+    hasher.write_u8(u8::from(options.repeated_words));
+    for regex in options.ignore_regexes.iter() {
+        hasher.write(regex.as_str().as_bytes());
+        hasher.write_u8(0);
+    }
+    for rule in options.dictionary_rules.iter() {
+        hasher.write(rule.pattern.as_bytes());
+        hasher.write(rule.dictionaries.join(",").as_bytes());
+        hasher.write_u8(0);
+    }
+    // cached results come from decoded text, so the encoding changes them
+    hasher.write(options.encoding.to_string().as_bytes());
+    hasher.write_u8(0);
+    for rule in options.encoding_rules.iter() {
+        hasher.write(rule.pattern.as_bytes());
+        hasher.write_u8(0);
+        hasher.write(rule.encoding.to_string().as_bytes());
+        hasher.write_u8(0);
+    }
+}
+
+fn hash_entry(entry: &Entry, hasher: &mut Fnv) {
+    hasher.write(entry.word.as_bytes());
+    hasher.write_u8(0);
+    hasher.write_u8(match entry.flags.case_sensitive {
+        Some(true) => 1,
+        Some(false) => 2,
+        None => 0,
+    });
+    hasher.write_u8(u8::from(entry.flags.forbidden));
+    hasher.write_u8(u8::from(entry.flags.no_suggest));
+}
+
+// wordlists and filters loaded once and shared by all checked texts
+pub struct Checker<'a> {
+    options: &'a Options,
+    dictionaries: Dictionaries,
+    ignore_list: Trie,
+    non_words: NonWordFilter,
+    style_rules: StyleRules,
+    variants: Variants,
+    variant: Option<Variant>,
+    cache: Option<ResultCache>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(options: &'a Options) -> Result<Self, String> {
+        let mut dictionaries = Dictionaries::new(options.dictionary_rules.clone());
+        let mut ignore_list = Trie::new();
+        // every loaded word and option changing results is part of the
+        // cache configuration
+        let mut hasher = Fnv::default();
+        hash_options(options, &mut hasher);
+
+        for ignore in &options.ignore {
+            for entry in handle_read_wordlist(ignore, None, options)? {
+                hasher.write(entry.word.as_bytes());
+                hasher.write_u8(0);
+                ignore_list.insert(&entry.word);
+            }
+        }
+        hasher.write_u8(1);
+        for wordlist in &options.wordlists {
+            let entries = handle_read_wordlist(&wordlist.path, Some(&ignore_list), options)?;
+            hasher.write(wordlist.name.as_bytes());
+            hasher.write_u8(1);
+            let dictionary = dictionaries.get_or_insert(&wordlist.name);
+            for entry in entries {
+                hash_entry(&entry, &mut hasher);
+                dictionary.insert_entry(&entry);
+            }
+        }
+        // words added from the editor belong to the default dictionary
+        let project_wordlist = Path::new(PROJECT_WORDLIST);
+        if project_wordlist.is_file() {
+            let entries = handle_read_wordlist(project_wordlist, Some(&ignore_list), options)?;
+            hasher.write(PROJECT_WORDLIST.as_bytes());
+            hasher.write_u8(1);
+            let dictionary = dictionaries.get_or_insert(DEFAULT_DICTIONARY);
+            for entry in entries {
+                hash_entry(&entry, &mut hasher);
+                dictionary.insert_entry(&entry);
+            }
+        }
+        let mut style_rules = StyleRules::new();
+        hasher.write_u8(2);
+        for path in &options.style_rules {
+            let content = read_wordlist_file(path)?;
+            for rule in style_rules.load(&String::from_utf8_lossy(&content), path)? {
+                hasher.write(rule.id.as_bytes());
+                hasher.write_u8(0);
+                hasher.write(rule.term.as_bytes());
+                hasher.write_u8(0);
+                hasher.write(rule.preferred.unwrap_or_default().as_bytes());
+                hasher.write_u8(0);
+            }
+        }
+        let variants = Variants::new();
+        let variant = match options.variant {
+            Some(VariantMode::Fixed(variant)) => Some(variant),
+            Some(VariantMode::Auto) => detect_variant(options, &variants),
+            None => None,
+        };
+        hasher.write(format!("{variant:?}").as_bytes());
+        for name in &options.builtin_dictionaries {
+            if let Some(words) = builtin::get_builtin(name) {
+                let entries = parse_wordlist(words, Path::new(name))?;
+                let entries = filter_wordlist(entries, Some(&ignore_list), options);
+                hasher.write(name.as_bytes());
+                hasher.write_u8(1);
+                let dictionary = dictionaries.get_or_insert(name);
+                for entry in entries {
+                    hash_entry(&entry, &mut hasher);
+                    dictionary.insert_entry(&entry);
+                }
+            }
+        }
+
+        Ok(Self {
+            options,
+            dictionaries,
+            ignore_list,
+            non_words: NonWordFilter::new(&options.ignore_regexes),
+            style_rules,
+            variants,
+            variant,
+            cache: options
+                .cache
+                .then(|| ResultCache::new(Path::new("."), hasher.finish())),
+        })
+    }
+
+    // looks up one word of token, the typo names the token when they differ
+    fn check_word(
+        &self,
+        word: &Token,
+        token: &Token,
+        dictionaries: &[&Dictionary],
+        line_num: usize,
+        case_sensitive: bool,
+    ) -> Option<Typo> {
+        let mut text = normalize_apostrophes(&word.text);
+
+        // contractions are looked up whole, possessives without the 's
+        if let Some(base) = text.strip_suffix("'s") {
+            if !matches!(
+                dictionary::lookup(dictionaries, &text, case_sensitive),
+                Lookup::Found
+            ) {
+                text = base.to_string();
+            }
+        }
+
+        if !is_word_correct(&text, &self.ignore_list, self.options) {
+            return None;
+        }
+
+        let kind = match dictionary::lookup(dictionaries, &text, case_sensitive) {
+            Lookup::Found => return self.check_variant(word, &text, token, line_num),
+            Lookup::WrongCase(expected) => TypoKind::WrongCase(expected),
+            Lookup::Forbidden => TypoKind::Forbidden,
+            Lookup::Missing => TypoKind::Misspelled,
+        };
+
+        Some(Typo {
+            word: word.text.clone(),
+            token: (word.text != token.text).then(|| token.text.clone()),
+            line: line_num,
+            column: word.column + 1,
+            kind,
+            fix: None,
+        })
+    }
+
+    // correct words spelled in the other variant, text is the word without
+    // a possessive 's. Only whole words get a fix, not parts of identifiers
+    fn check_variant(
+        &self,
+        word: &Token,
+        text: &str,
+        token: &Token,
+        line_num: usize,
+    ) -> Option<Typo> {
+        let expected = self.variant?;
+        let preferred = match_case(text, self.variants.other_spelling(text, expected)?);
+        let start = Position {
+            line: line_num,
+            column: word.column + 1,
+        };
+        let end = Position {
+            line: line_num,
+            column: start.column + text.chars().count(),
+        };
+
+        let whole_word = word.text == token.text;
+
+        Some(Typo {
+            word: word.text.clone(),
+            token: (!whole_word).then(|| token.text.clone()),
+            line: line_num,
+            column: start.column,
+            fix: whole_word.then(|| Fix {
+                start,
+                end,
+                replacement: preferred.clone(),
+            }),
+            kind: TypoKind::Variant {
+                expected,
+                preferred,
+            },
+        })
+    }
+
+    fn check_token(
+        &self,
+        token: &Token,
+        dictionaries: &[&Dictionary],
+        line_num: usize,
+    ) -> Vec<Typo> {
+        let mut typos: Vec<Typo> = Vec::new();
+
+        if self.non_words.is_non_word(&token.text) {
+            return typos;
+        }
+        // style rules apply to whole tokens like e-mail, their parts and the
+        // words of identifiers, even when they are spelled correctly
+        if let Some(typo) = self.style_rules.check(token, token, line_num) {
+            typos.push(typo);
+            return typos;
+        }
+
+        // whole words written exactly like a dictionary entry, e.g. brand names
+        // in camel case or e-mail, are never split
+        let text = normalize_apostrophes(&token.text);
+        match dictionary::lookup(dictionaries, &text, true) {
+            Lookup::Found => {
+                typos.extend(self.check_variant(token, &text, token, line_num));
+                return typos;
+            }
+            Lookup::Forbidden if split_compound(token).len() > 1 => {
+                typos.push(Typo {
+                    word: token.text.clone(),
+                    token: None,
+                    line: line_num,
+                    column: token.column + 1,
+                    kind: TypoKind::Forbidden,
+                    fix: None,
+                });
+                return typos;
+            }
+            _ => {}
+        }
+
+        let parts = split_compound(token);
+        let compound = parts.len() > 1;
+
+        for part in parts.iter() {
+            if self.non_words.is_non_word(&part.text) {
+                continue;
+            }
+
+            if compound {
+                if let Some(typo) = self.style_rules.check(part, token, line_num) {
+                    typos.push(typo);
+                    continue;
+                }
+                if let Lookup::Found = dictionary::lookup(dictionaries, &part.text, true) {
+                    typos.extend(self.check_variant(part, &part.text, token, line_num));
+                    continue;
+                }
+            }
+
+            match split_identifier(part) {
+                // identifier casing follows the naming convention, so its parts
+                // are compared case insensitively
+                Some(words) => {
+                    for word in words.iter() {
+                        if let Some(typo) = self.style_rules.check(word, token, line_num) {
+                            typos.push(typo);
+                            continue;
+                        }
+                        typos.extend(self.check_word(word, token, dictionaries, line_num, false));
+                    }
+                }
+                None => {
+                    typos.extend(self.check_word(part, token, dictionaries, line_num, !compound))
+                }
+            }
+        }
+
+        typos
+    }
+
+    // checks text as if it was the content of path, the path only selects
+    // dictionaries and doesn't have to exist. Typos are sorted by position
+    pub fn check_text(&self, text: &str, path: &Path) -> Vec<Typo> {
+        self.check_text_cached(text, path, None)
+    }
+
+    // misspellings of a line only depend on its text, so with a cache lines
+    // checked before aren't looked up again. Lines no longer in the text are
+    // dropped from the cache
+    pub fn check_text_cached(
+        &self,
+        text: &str,
+        path: &Path,
+        mut cache: Option<&mut LineCache>,
+    ) -> Vec<Typo> {
+        let dictionaries = self.dictionaries.select(path);
+        let mut typos: Vec<Typo> = Vec::new();
+        let mut repeated_words = RepeatedWords::new();
+        let mut used_lines: HashSet<String> = HashSet::new();
+
+        for (num, line) in text.lines().enumerate() {
+            let line = self.non_words.mask_line(line);
+            let tokens = get_words_from_line(&line);
+            let check_tokens = |line_num: usize| -> Vec<Typo> {
+                tokens
+                    .iter()
+                    .flat_map(|token| self.check_token(token, &dictionaries, line_num))
+                    .collect()
+            };
+
+            match cache.as_deref_mut() {
+                Some(cache) => {
+                    let cached = cache
+                        .entry(line.to_string())
+                        .or_insert_with(|| check_tokens(0));
+                    typos.extend(cached.iter().cloned().map(|mut typo| {
+                        typo.line = num + 1;
+                        if let Some(fix) = typo.fix.as_mut() {
+                            fix.start.line = num + 1;
+                            fix.end.line = num + 1;
+                        }
+                        typo
+                    }));
+                    used_lines.insert(line.to_string());
+                }
+                None => typos.extend(check_tokens(num + 1)),
+            }
+
+            if self.options.repeated_words {
+                typos.extend(repeated_words.check_line(&line, &tokens, num + 1));
+            }
+        }
+
+        if let Some(cache) = cache {
+            cache.retain(|line, _| used_lines.contains(line));
+        }
+
+        typos.sort_by_key(|typo| (typo.line, typo.column));
+        typos
+    }
+
+    // words added while running, e.g. from an editor, go to the default
+    // dictionary
+    pub fn add_word(&mut self, entry: &Entry) {
+        self.dictionaries
+            .get_or_insert(DEFAULT_DICTIONARY)
+            .insert_entry(entry);
+    }
+
+    // closest dictionary words, path selects dictionaries like in check_text
+    pub fn suggest(&self, word: &str, path: &Path) -> Vec<String> {
+        dictionary::suggest(&self.dictionaries.select(path), word, MAX_SUGGESTIONS)
+    }
+}
+
+fn count_words(text: &str) -> u64 {
+    text.lines()
+        .map(|line| get_words_from_line(line).len() as u64)
+        .sum()
+}
+
+// path is used for rules and messages, fixes are written to it only if
+// the content was read from it. With changed lines only typos on them
+// are reported
+fn check_content(
     path: &Path,
-    trie: &Trie,
-    ignore_list: &Trie,
-    options: &Options,
+    content: &[u8],
+    checker: &Checker,
+    changed_lines: Option<&[Range<usize>]>,
     stats: &mut CheckStats,
+    writable: bool,
 ) -> Result<(), io::Error> {
-    let file = File::open(path)?;
-    let lines = BufReader::new(file).lines();
-
-    let mut counter: u64 = 0;
-
-    let parsing_functions = [
-        parse_variables::parse_camel_case,
-        parse_variables::parse_snake_case,
-    ];
-
-    for (num, line) in lines.map_while(Result::ok).enumerate() {
-        let line: &str = line.as_str();
-        let words = get_words_from_line(line);
-
-        for word in words {
-            let mut parsed = false;
-            for parsing_func in parsing_functions {
-                if let Some(parsed_words) = parsing_func(&word) {
-                    for word in parsed_words {
-                        check_word(&word, trie, ignore_list, &mut counter, options, &num);
-                    }
-                    parsed = true;
-                }
+    let options = checker.options;
+    let encoding = select_encoding(content, path, &options.encoding_rules, options.encoding);
+
+    // utf-16 text is full of NUL bytes, so it is never sniffed
+    if !encoding.is_utf16() && is_binary(content) {
+        note(
+            options,
+            &format!("  ! {}: skipped binary file", path.display()),
+        );
+        stats.binary_files_skipped += 1;
+        return Ok(());
+    }
+    stats.files_checked += 1;
+
+    let key = ResultCache::key(path, content);
+    let cached = checker.cache.as_ref().and_then(|cache| cache.get(key));
+    let result = match cached {
+        Some(result) => {
+            stats.cached_files += 1;
+            result
+        }
+        None => {
+            let decoded = decode(content, encoding);
+            let result = CachedResult {
+                invalid_line: decoded.invalid_line,
+                words: count_words(&decoded.text),
+                lines: decoded.text.lines().count() as u64,
+                typos: checker.check_text(&decoded.text, path),
+            };
+            if let Some(cache) = &checker.cache {
+                cache.put(key, &result);
             }
+            result
+        }
+    };
+
+    if let Some(line) = result.invalid_line {
+        note(
+            options,
+            &format!(
+                "  ! {}: invalid {encoding} from line {line}, invalid bytes were replaced",
+                path.display()
+            ),
+        );
+        stats.invalid_encoding_files += 1;
+    }
 
-            if !parsed {
-                check_word(
-                    &word.to_lowercase(),
-                    trie,
-                    ignore_list,
-                    &mut counter,
-                    options,
-                    &num,
-                )
+    let mut typos = result.typos;
+    if let Some(ranges) = changed_lines {
+        typos.retain(|typo| ranges.iter().any(|range| range.contains(&typo.line)));
+    }
+
+    stats.words_checked += result.words;
+    stats.lines_checked += result.lines;
+    stats.add_typos(path, &typos);
+    match prints_files(options) {
+        true if !typos.is_empty() => {
+            let renderer = Renderer::new(options);
+            let decoded = decode(content, encoding);
+            let lines: Vec<&str> = decoded.text.lines().collect();
+            for typo in typos.iter() {
+                let suggestions = match typo.kind {
+                    TypoKind::Misspelled | TypoKind::Forbidden => checker.suggest(&typo.word, path),
+                    _ => Vec::new(),
+                };
+                print!("{}", renderer.render(typo, &lines, &suggestions));
             }
         }
+        true => {}
+        false => stats.reported.push((path.to_path_buf(), typos.clone())),
     }
 
-    stats.typos_num += counter;
+    // writing back a lossy decoded file would destroy the invalid bytes
+    if options.fix && writable && result.invalid_line.is_none() {
+        let fixes: Vec<&Fix> = typos.iter().filter_map(|typo| typo.fix.as_ref()).collect();
+        if !fixes.is_empty() {
+            let decoded = decode(content, encoding);
+            stats.fixes_applied += fix::fix_file(path, &decoded, &fixes)? as u64;
+        }
+    }
 
     Ok(())
 }
 
-fn handle_feed_trie(
-    trie: &mut Trie,
-    ignore_list: Option<&mut Trie>,
-    files: &Vec<PathBuf>,
+fn check_correctness(
+    path: &Path,
+    checker: &Checker,
+    diff: Option<&ChangedLines>,
+    stats: &mut CheckStats,
+) -> Result<(), io::Error> {
+    let Some(diff) = diff else {
+        let content = fs::read(path)?;
+        return check_content(path, &content, checker, None, stats, true);
+    };
+    let Some(changed_lines) = diff.lines(path) else {
+        return Ok(());
+    };
+
+    // staged positions refer to the index, so they can't fix the working tree
+    let staged = checker.options.staged;
+    let content = if staged {
+        diff.staged_content(path)?
+    } else {
+        fs::read(path)?
+    };
+    check_content(path, &content, checker, Some(changed_lines), stats, !staged)
+}
+
+fn check_stdin(checker: &Checker, stats: &mut CheckStats) -> Result<(), io::Error> {
+    let mut content = Vec::new();
+    io::stdin().read_to_end(&mut content)?;
+
+    let path = checker
+        .options
+        .stdin_filename
+        .as_deref()
+        .unwrap_or(Path::new(STDIN_TARGET));
+    check_content(path, &content, checker, None, stats, false)
+}
+
+fn read_wordlist_file(wordlist: &Path) -> Result<Vec<u8>, String> {
+    fs::read(wordlist).map_err(|error| {
+        if error.kind() == ErrorKind::NotFound {
+            format!(
+                "Error occurred reading {} file not found",
+                wordlist.display()
+            )
+        } else if error.kind() == ErrorKind::PermissionDenied {
+            format!(
+                "Error occurred reading {} permission denied",
+                wordlist.display()
+            )
+        } else {
+            format!("Unknow error occurred reading {}", wordlist.display())
+        }
+    })
+}
+
+fn handle_read_wordlist(
+    wordlist: &Path,
+    ignore_list: Option<&Trie>,
     options: &Options,
-) {
-    for wordlist in files {
-        feed_trie(wordlist, &ignore_list, trie, options).unwrap_or_else(|error| {
-            if error.kind() == ErrorKind::NotFound {
-                println!(
-                    "Error occurred reading {} file not found",
-                    wordlist.display()
-                );
-            } else if error.kind() == ErrorKind::PermissionDenied {
-                println!(
-                    "Error occurred reading {} permission denied",
-                    wordlist.display()
-                );
-            } else {
-                println!("Unknow error occurred reading {}", wordlist.display());
-            }
-            process::exit(1);
-        });
+) -> Result<Vec<Entry>, String> {
+    let content = read_wordlist_file(wordlist)?;
+    let entries = parse_wordlist(&String::from_utf8_lossy(&content), wordlist)?;
+
+    Ok(filter_wordlist(entries, ignore_list, options))
+}
+
+// returns the number of typos found in the message
+fn check_commit_message(path: &Path, checker: &Checker) -> Result<usize, io::Error> {
+    let content = fs::read(path)?;
+    let message = clean_commit_message(&String::from_utf8_lossy(&content));
+    let typos = checker.check_text(&message, path);
+
+    for typo in typos.iter() {
+        let suggestions = match typo.kind {
+            TypoKind::Misspelled | TypoKind::Forbidden => checker.suggest(&typo.word, path),
+            _ => Vec::new(),
+        };
+
+        if suggestions.is_empty() {
+            println!("{typo}");
+        } else {
+            println!("{typo} (did you mean {}?)", suggestions.join(", "));
+        }
     }
+
+    Ok(typos.len())
 }
 
 fn handle_correctness_check(
     path: &Path,
-    trie: &Trie,
-    ignore_list: &Trie,
+    checker: &Checker,
+    diff: Option<&ChangedLines>,
     stats: &mut CheckStats,
-    options: &Options,
 ) {
-    if let Err(error) = check_correctness(path, trie, ignore_list, options, stats) {
-        match error.kind() {
-            ErrorKind::PermissionDenied => {
-                println!(
-                    "Error occurred reading {} permission denied",
-                    path.display()
-                )
-            }
-            _ => println!("Unknown error occurred reading {}", path.display()),
+    let result = if path == Path::new(STDIN_TARGET) {
+        check_stdin(checker, stats)
+    } else {
+        check_correctness(path, checker, diff, stats)
+    };
+
+    if let Err(error) = result {
+        let message = match error.kind() {
+            ErrorKind::PermissionDenied => format!(
+                "Error occurred reading {} permission denied",
+                path.display()
+            ),
+            _ => format!("Unknown error occurred reading {}", path.display()),
         };
+        note(checker.options, &message);
         stats.errors += 1;
     }
 }
 
-fn show_result(stats: &CheckStats) {
-    println!("===SUCCESSFULLY FINISHED===");
-    println!("->Files checked: {}", stats.files_checked);
-    println!("->Dirs checked: {}", stats.dirs_checked);
-    println!("->Typos found: {}", stats.typos_num);
-    println!("->Errors: {}", stats.errors);
-    println!("===THANKS FOR USING THIS SOFTWARE!===");
-}
+const EXCLUDED_PATH: &str = "excluded path";
+const EXCLUDED_EXTENSION: &str = "excluded extension";
+const OTHER_EXTENSION: &str = "extension not selected";
+const UNCHANGED: &str = "unchanged";
 
 fn skip_file(file: &Path, options: &Options) -> Result<bool, io::Error> {
+    Ok(skip_reason(file, options)?.is_some())
+}
+
+// rule skipping the file, if any
+fn skip_reason(file: &Path, options: &Options) -> Result<Option<&'static str>, io::Error> {
     let canonicalized_target = canonicalize(file)?;
 
     for path_to_exclude in options.paths_to_exclude.iter() {
         let canonicalized_file = canonicalize(path_to_exclude)?;
         if canonicalized_file == canonicalized_target {
-            return Ok(true);
+            return Ok(Some(EXCLUDED_PATH));
         }
     }
 
@@ -199,67 +719,252 @@ fn skip_file(file: &Path, options: &Options) -> Result<bool, io::Error> {
                     .extensions_to_exclude
                     .contains(&extension.to_string())
             {
-                return Ok(true);
+                return Ok(Some(EXCLUDED_EXTENSION));
             }
 
             if !options.extensions.is_empty()
                 && !options.extensions.contains(&extension.to_string())
             {
-                return Ok(true);
+                return Ok(Some(OTHER_EXTENSION));
             }
         }
         None => {
             if !options.extensions.is_empty() {
-                return Ok(true);
+                return Ok(Some(OTHER_EXTENSION));
             }
         }
     }
 
-    Ok(false)
+    Ok(None)
 }
 
-pub fn spell_check(options: &Options) -> Result<(), io::Error> {
-    let mut words_trie = Trie::new();
-    let mut ignore_list = Trie::new();
-    let mut stats = CheckStats::new();
+fn load_diff(options: &Options) -> Option<ChangedLines> {
+    if options.diff_base.is_none() && !options.staged {
+        return None;
+    }
 
-    for target in &options.targets {
-        handle_feed_trie(&mut ignore_list, None, &options.ignore, options);
-        handle_feed_trie(
-            &mut words_trie,
-            Some(&mut ignore_list),
-            &options.wordlists,
-            options,
-        );
+    match ChangedLines::new(options.diff_base.as_deref(), options.staged) {
+        Ok(diff) => Some(diff),
+        Err(error) => {
+            println!("Error occurred reading git diff: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn check_targets(
+    options: &Options,
+    checker: &Checker,
+    diff: Option<&ChangedLines>,
+    stats: &mut CheckStats,
+) -> Result<(), io::Error> {
+    // files without changes are skipped entirely
+    let skipped = |file: &Path| -> Result<Option<&'static str>, io::Error> {
+        match skip_reason(file, options)? {
+            Some(reason) => Ok(Some(reason)),
+            None if file.is_file() && diff.is_some_and(|diff| diff.lines(file).is_none()) => {
+                Ok(Some(UNCHANGED))
+            }
+            None => Ok(None),
+        }
+    };
+    let text = prints_files(options);
 
-        if target.is_file() {
-            if skip_file(target, options)? {
+    for target in &options.targets {
+        if target == Path::new(STDIN_TARGET) {
+            handle_correctness_check(target, checker, None, stats);
+        } else if target.is_file() {
+            if let Some(reason) = skipped(target)? {
+                stats.skip(reason);
                 continue;
             }
-            stats.files_checked += 1;
-            handle_correctness_check(target, &words_trie, &ignore_list, &mut stats, options)
+            handle_correctness_check(target, checker, diff, stats);
         } else if target.is_dir() {
             let files = get_files(target);
             for file in files.into_iter().filter_map(|x| x.ok()) {
                 let file = file.path();
-
-                if skip_file(file, options)? {
+                if is_cache_path(file) {
+                    continue;
+                }
+                if let Some(reason) = skipped(file)? {
+                    if file.is_file() {
+                        stats.skip(reason);
+                    }
                     continue;
                 }
 
                 if file.is_file() {
-                    println!("-> {}", file.display());
-                    handle_correctness_check(file, &words_trie, &ignore_list, &mut stats, options);
-                    stats.files_checked += 1;
+                    if text {
+                        println!("-> {}", file.display());
+                    }
+                    handle_correctness_check(file, checker, diff, stats);
                 } else if file.is_dir() {
                     stats.dirs_checked += 1;
                 }
-                println!();
+                if text {
+                    println!();
+                }
             }
         }
     }
 
-    show_result(&stats);
-
     Ok(())
 }
+
+pub fn spell_check(options: &Options) -> Result<CheckStats, io::Error> {
+    let mut stats = CheckStats::new();
+
+    if let Some(hook) = &options.hook {
+        match hook::install_hook(hook, options.force) {
+            Ok(path) => println!("Installed {} hook in {}", hook.kind, path.display()),
+            Err(error) => {
+                println!("{error}");
+                process::exit(1);
+            }
+        }
+        return Ok(stats);
+    }
+
+    if let Some(command) = &options.dict {
+        dict::run(command, options);
+        return Ok(stats);
+    }
+
+    let checker = Checker::new(options).unwrap_or_else(|error| {
+        println!("{error}");
+        process::exit(1);
+    });
+
+    if let Some(command) = &options.learn {
+        learn::learn(command, &checker, options, &mut stats)?;
+        return Ok(stats);
+    }
+
+    if options.lsp {
+        let stdin = io::stdin();
+        lsp::run(checker, &mut stdin.lock(), &mut io::stdout().lock())?;
+        return Ok(stats);
+    }
+
+    if options.commit_msg {
+        let path = &options.targets[0];
+        match check_commit_message(path, &checker) {
+            Ok(0) => return Ok(stats),
+            Ok(typos) => println!("commit message has {typos} typo(s)"),
+            Err(_) => println!("Error occurred reading {}", path.display()),
+        }
+        process::exit(1);
+    }
+
+    if options.watch {
+        return watch::watch(options, checker);
+    }
+
+    let diff = load_diff(options);
+    check_targets(options, &checker, diff.as_ref(), &mut stats)?;
+    show_result(&stats, &checker);
+
+    if let Some(cache) = &checker.cache {
+        cache.prune();
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::Hasher;
+    use std::path::Path;
+
+    use crate::spell_checker::cache::Fnv;
+    use crate::spell_checker::options::Options;
+    use crate::spell_checker::stats::CheckStats;
+    use crate::spell_checker::typo::TypoKind;
+    use crate::spell_checker::{check_content, hash_options, Checker};
+
+    #[test]
+    fn check_text_in_memory() {
+        let argv = ["rscheck", "-t", "-", "-rw"].map(str::to_string);
+        let options = Options::create(argv).unwrap();
+        let checker = Checker::new(&options).unwrap();
+
+        let typos = checker.check_text("some speling\nthe the end", Path::new("notes.txt"));
+        assert_eq!(typos.len(), 2);
+        assert_eq!(typos[0].word, "speling");
+        assert_eq!((typos[0].line, typos[0].column), (1, 6));
+        assert!(matches!(typos[1].kind, TypoKind::Repeated(_)));
+    }
+
+    #[test]
+    fn check_default_dictionaries() {
+        let options = Options::default();
+        let checker = Checker::new(&options).unwrap();
+
+        let text = "After the meeting we walked home before it got dark. The black dog \
+            was sleeping under an old tree, and nobody wanted to wake him. Her German \
+            friends said they would come back tomorrow because the weather should be \
+            better, although nobody really believed it. I'm sure you'll agree that \
+            these simple sentences shouldn't contain any typos.";
+        let typos = checker.check_text(text, Path::new("notes.md"));
+        let words: Vec<&str> = typos.iter().map(|typo| typo.word.as_str()).collect();
+        assert!(words.is_empty(), "{words:?}");
+    }
+
+    #[test]
+    fn check_variant_fixes() {
+        let argv = ["rscheck", "-t", "-", "--variant", "us"].map(str::to_string);
+        let options = Options::create(argv).unwrap();
+        let checker = Checker::new(&options).unwrap();
+
+        let typos = checker.check_text("the colour of setColour", Path::new("notes.txt"));
+        assert_eq!(typos.len(), 2);
+        assert_eq!(typos[0].fix.as_ref().unwrap().replacement, "color");
+        assert_eq!(typos[1].token.as_deref(), Some("setColour"));
+        assert!(typos[1].fix.is_none());
+    }
+
+    #[test]
+    fn check_content_encodings() {
+        let argv = ["rscheck", "-t", "-", "--no-cache"].map(str::to_string);
+        let options = Options::create(argv).unwrap();
+        let checker = Checker::new(&options).unwrap();
+        let mut stats = CheckStats::new();
+        let check = |content: &[u8], stats: &mut CheckStats| {
+            let path = Path::new("notes.txt");
+            check_content(path, content, &checker, None, stats, false).unwrap()
+        };
+
+        check(b"GIF89a some image", &mut stats);
+        check(b"some\x00binary", &mut stats);
+        assert_eq!((stats.binary_files_skipped, stats.files_checked), (2, 0));
+
+        // utf-16 is detected from the bom and its NUL bytes are no binary
+        check(b"\xFF\xFEs\x00p\x00e\x00l\x00i\x00n\x00g\x00", &mut stats);
+        check(b"valid line\ninvalid \xC3\x28 byte\n", &mut stats);
+        assert_eq!((stats.binary_files_skipped, stats.files_checked), (2, 2));
+        assert_eq!(stats.invalid_encoding_files, 1);
+        assert_eq!(stats.typos_num, 1);
+    }
+
+    #[test]
+    fn check_cache_configuration() {
+        let hash = |args: &[&str]| {
+            let argv = ["rscheck", "-t", "-"]
+                .iter()
+                .chain(args)
+                .map(|arg| arg.to_string());
+            let mut hasher = Fnv::default();
+            hash_options(&Options::create(argv).unwrap(), &mut hasher);
+            hasher.finish()
+        };
+
+        let default = hash(&[]);
+        assert_eq!(default, hash(&[]));
+        assert_ne!(default, hash(&["-enc", "windows-1252"]));
+        assert_ne!(default, hash(&["-er", "*.rc=utf-16le"]));
+        assert_ne!(
+            hash(&["-er", "*.rc=utf-16le"]),
+            hash(&["-er", "*.rc=latin1"])
+        );
+    }
+}
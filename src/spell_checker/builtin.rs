@@ -0,0 +1,19 @@
+pub const ENGLISH: &str = "en";
+pub const CODE: &str = "code";
+pub const NONE: &str = "none";
+
+const BUILTIN_DICTIONARIES: [(&str, &str); 2] = [
+    (ENGLISH, include_str!("../../dictionaries/en.txt")),
+    (CODE, include_str!("../../dictionaries/code.txt")),
+];
+
+pub fn get_builtin(name: &str) -> Option<&'static str> {
+    BUILTIN_DICTIONARIES
+        .iter()
+        .find(|(builtin_name, _)| *builtin_name == name)
+        .map(|(_, words)| *words)
+}
+
+pub fn is_builtin(name: &str) -> bool {
+    get_builtin(name).is_some()
+}
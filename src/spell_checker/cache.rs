@@ -0,0 +1,286 @@
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde_json::{json, Value};
+
+use super::typo::{Fix, Position, Typo, TypoKind};
+use super::variants::Variant;
+
+pub const CACHE_DIR: &str = ".rscheck-cache";
+
+// entries not used for this long are removed when pruning
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+// 64 bit FNV-1a, stable between runs unlike the std hasher
+pub struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+pub struct CachedResult {
+    pub invalid_line: Option<usize>,
+    pub words: u64,
+    pub lines: u64,
+    pub typos: Vec<Typo>,
+}
+
+// results are stored per configuration, the key of an entry is the hash of
+// the file path and content, the path selects dictionaries and encodings
+pub struct ResultCache {
+    root: PathBuf,
+    dir: PathBuf,
+}
+
+fn position_to_json(position: &Position) -> Value {
+    json!([position.line, position.column])
+}
+
+fn position_from_json(value: &Value) -> Option<Position> {
+    Some(Position {
+        line: value[0].as_u64()? as usize,
+        column: value[1].as_u64()? as usize,
+    })
+}
+
+pub fn typo_to_json(typo: &Typo) -> Value {
+    let mut value = json!({
+        "word": typo.word,
+        "token": typo.token,
+        "line": typo.line,
+        "column": typo.column,
+    });
+
+    value["kind"] = json!(typo.kind.name());
+    match &typo.kind {
+        TypoKind::WrongCase(expected) => value["expected"] = json!(expected),
+        TypoKind::Style { rule, preferred } => {
+            value["rule"] = json!(rule);
+            value["preferred"] = json!(preferred);
+        }
+        TypoKind::Variant {
+            expected,
+            preferred,
+        } => {
+            value["expected"] = json!(expected.to_string());
+            value["preferred"] = json!(preferred);
+        }
+        TypoKind::Repeated(first) => value["first"] = position_to_json(first),
+        TypoKind::Misspelled | TypoKind::Forbidden => {}
+    }
+    if let Some(fix) = &typo.fix {
+        value["fix"] = json!({
+            "start": position_to_json(&fix.start),
+            "end": position_to_json(&fix.end),
+            "replacement": fix.replacement,
+        });
+    }
+
+    value
+}
+
+fn typo_from_json(value: &Value) -> Option<Typo> {
+    let kind = match value["kind"].as_str()? {
+        "misspelled" => TypoKind::Misspelled,
+        "wrong_case" => TypoKind::WrongCase(value["expected"].as_str()?.to_string()),
+        "forbidden" => TypoKind::Forbidden,
+        "style" => TypoKind::Style {
+            rule: value["rule"].as_str()?.to_string(),
+            preferred: value["preferred"].as_str().map(str::to_string),
+        },
+        "variant" => TypoKind::Variant {
+            expected: Variant::from_name(value["expected"].as_str()?)?,
+            preferred: value["preferred"].as_str()?.to_string(),
+        },
+        "repeated" => TypoKind::Repeated(position_from_json(&value["first"])?),
+        _ => return None,
+    };
+    let fix = match value.get("fix") {
+        Some(fix) => Some(Fix {
+            start: position_from_json(&fix["start"])?,
+            end: position_from_json(&fix["end"])?,
+            replacement: fix["replacement"].as_str()?.to_string(),
+        }),
+        None => None,
+    };
+
+    Some(Typo {
+        word: value["word"].as_str()?.to_string(),
+        token: value["token"].as_str().map(str::to_string),
+        line: value["line"].as_u64()? as usize,
+        column: value["column"].as_u64()? as usize,
+        kind,
+        fix,
+    })
+}
+
+impl ResultCache {
+    pub fn new(root: &Path, config_hash: u64) -> Self {
+        let root = root.join(CACHE_DIR);
+        let dir = root.join(format!("{config_hash:016x}"));
+
+        Self { root, dir }
+    }
+
+    pub fn key(path: &Path, content: &[u8]) -> u64 {
+        let mut hasher = Fnv::default();
+        hasher.write(path.to_string_lossy().as_bytes());
+        hasher.write_u8(0);
+        hasher.write(content);
+        hasher.finish()
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{key:016x}.json"))
+    }
+
+    // a used entry is touched so pruning keeps it
+    pub fn get(&self, key: u64) -> Option<CachedResult> {
+        let path = self.entry_path(key);
+        let value: Value = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+
+        let typos = value["typos"]
+            .as_array()?
+            .iter()
+            .map(typo_from_json)
+            .collect::<Option<Vec<Typo>>>()?;
+
+        if let Ok(file) = File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+
+        Some(CachedResult {
+            invalid_line: value["invalid_line"].as_u64().map(|line| line as usize),
+            words: value["words"].as_u64().unwrap_or(0),
+            lines: value["lines"].as_u64().unwrap_or(0),
+            typos,
+        })
+    }
+
+    // the cache is only an optimization, failing to write it is ignored
+    pub fn put(&self, key: u64, result: &CachedResult) {
+        if !self.root.exists() {
+            let _ = fs::create_dir_all(&self.root);
+            let _ = fs::write(self.root.join(".gitignore"), "*\n");
+        }
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+
+        let value = json!({
+            "invalid_line": result.invalid_line,
+            "words": result.words,
+            "lines": result.lines,
+            "typos": result.typos.iter().map(typo_to_json).collect::<Vec<Value>>(),
+        });
+        let _ = fs::write(self.entry_path(key), value.to_string());
+    }
+
+    // removes results of other configurations and entries unused for a week
+    pub fn prune(&self) {
+        let Ok(dirs) = fs::read_dir(&self.root) else {
+            return;
+        };
+
+        for dir in dirs.filter_map(Result::ok) {
+            let path = dir.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path != self.dir {
+                let _ = fs::remove_dir_all(&path);
+                continue;
+            }
+
+            let Ok(entries) = fs::read_dir(&path) else {
+                continue;
+            };
+            for entry in entries.filter_map(Result::ok) {
+                let unused = entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_some_and(|age| age > MAX_AGE);
+                if unused {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+}
+
+pub fn is_cache_path(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == CACHE_DIR)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::spell_checker::cache::{CachedResult, ResultCache};
+    use crate::spell_checker::typo::{Fix, Position, Typo, TypoKind};
+
+    #[test]
+    fn check_cache_round_trip() {
+        let root = std::env::temp_dir().join(format!("rscheck-cache-{}", std::process::id()));
+        let cache = ResultCache::new(&root, 1);
+        let key = ResultCache::key("a.md".as_ref(), b"the the");
+        assert_ne!(key, ResultCache::key("b.md".as_ref(), b"the the"));
+        assert!(cache.get(key).is_none());
+
+        let position = Position { line: 1, column: 4 };
+        cache.put(
+            key,
+            &CachedResult {
+                invalid_line: Some(2),
+                words: 3,
+                lines: 1,
+                typos: vec![Typo {
+                    word: "the".to_string(),
+                    token: None,
+                    line: 1,
+                    column: 5,
+                    kind: TypoKind::Repeated(Position { line: 1, column: 1 }),
+                    fix: Some(Fix {
+                        start: position,
+                        end: Position { line: 1, column: 8 },
+                        replacement: String::new(),
+                    }),
+                }],
+            },
+        );
+
+        let cached = cache.get(key).unwrap();
+        assert_eq!(cached.invalid_line, Some(2));
+        assert_eq!((cached.words, cached.lines), (3, 1));
+        assert_eq!(cached.typos.len(), 1);
+        assert_eq!(cached.typos[0].column, 5);
+        assert!(matches!(cached.typos[0].kind, TypoKind::Repeated(_)));
+        assert!(cached.typos[0].fix.as_ref().unwrap().start == position);
+
+        // entries of other configurations are pruned
+        ResultCache::new(&root, 2).prune();
+        assert!(cache.get(key).is_none());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
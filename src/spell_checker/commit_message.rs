@@ -0,0 +1,95 @@
+use regex::Regex;
+
+// marks the start of the diff added by git commit -v
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+// issue references like #12, owner/repo#12 or PROJ-12
+const ISSUE_REFERENCE: &str = r"(?:[\w.-]+/[\w.-]+)?#\d+\b|\b[A-Z][A-Z0-9]+-\d+\b";
+
+const TRAILER: &str = r"^[A-Za-z0-9][A-Za-z0-9-]*: ";
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with('#')
+}
+
+fn is_diff_start(line: &str) -> bool {
+    (is_comment(line) && line.contains(SCISSORS)) || line.starts_with("diff --git ")
+}
+
+// text git doesn't keep or which isn't prose is blanked, line numbers stay
+// the same as in the message file
+pub fn clean_commit_message(message: &str) -> String {
+    let issue_reference = Regex::new(ISSUE_REFERENCE).unwrap();
+    let trailer = Regex::new(TRAILER).unwrap();
+
+    let mut lines: Vec<&str> = message
+        .lines()
+        .take_while(|line| !is_diff_start(line))
+        .map(|line| if is_comment(line) { "" } else { line })
+        .collect();
+
+    // trailers are the last paragraph, unless the message has just one
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |index| index + 1);
+    let start = lines[..end]
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map_or(0, |index| index + 1);
+    let has_body = lines[..start].iter().any(|line| !line.trim().is_empty());
+
+    if has_body && lines[start..end].iter().all(|line| trailer.is_match(line)) {
+        for line in lines[start..end].iter_mut() {
+            *line = "";
+        }
+    }
+
+    lines
+        .iter()
+        .map(|line| {
+            issue_reference
+                .replace_all(line, |captures: &regex::Captures| {
+                    " ".repeat(captures[0].chars().count())
+                })
+                .into_owned()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::commit_message::clean_commit_message;
+
+    #[test]
+    fn check_commit_message_cleaning() {
+        let message = "Fix parser, closes #12 and PROJ-7
+
+Body mentions owner/repo#3.
+
+Signed-off-by: Some One <some@one.org>
+Co-authored-by: Other <other@one.org>
+# Please enter the commit message
+# ------------------------ >8 ------------------------
+diff --git a/a.txt b/a.txt
++wrold
+";
+        let expected = [
+            format!("Fix parser, closes {} and {}", " ".repeat(3), " ".repeat(6)),
+            String::new(),
+            format!("Body mentions {}.", " ".repeat(12)),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ]
+        .join("\n");
+        assert_eq!(clean_commit_message(message), expected);
+
+        assert_eq!(
+            clean_commit_message("Note: subject only"),
+            "Note: subject only"
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::trie::Trie;
+
+use super::handle_read_wordlist;
+use super::options::Options;
+use super::wordlist::parse_line;
+
+pub const ADD: &str = "add";
+pub const REMOVE: &str = "remove";
+pub const SORT: &str = "sort";
+pub const DEDUPE: &str = "dedupe";
+pub const LINT: &str = "lint";
+
+pub struct DictCommand {
+    pub action: String,
+    // words for add and remove, wordlists for the other actions
+    pub args: Vec<String>,
+    pub file: Option<PathBuf>,
+}
+
+// case insensitive order, words differing only in case are ordered by their
+// bytes so the order is always the same
+pub fn compare_words(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+// the word a line is sorted and compared by, None for blank and comment
+// lines. Lines with syntax errors are kept as they are
+fn entry_word(line: &str) -> Option<String> {
+    match parse_line(line) {
+        Ok(entry) => entry.map(|entry| entry.word),
+        Err(_) => Some(line.trim().to_string()),
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+fn is_sorted(lines: &[String]) -> bool {
+    let words: Vec<String> = lines.iter().filter_map(|line| entry_word(line)).collect();
+    words
+        .windows(2)
+        .all(|pair| compare_words(&pair[0], &pair[1]) != Ordering::Greater)
+}
+
+// chars the tokenizer can keep inside a token
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '_' | '\'' | '-' | '.' | '/')
+}
+
+fn read_words(path: &Path) -> io::Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content.lines().map(str::to_string).collect()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+fn write_words(path: &Path, words: &[String]) -> io::Result<()> {
+    let mut content = words.join("\n");
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    fs::write(path, content)
+}
+
+fn add(words: &[String], path: &Path) -> io::Result<()> {
+    let mut lines = read_words(path)?;
+    // an unsorted file gets new words at the end to keep its order
+    let sorted = is_sorted(&lines);
+    let mut added = 0;
+
+    for word in words {
+        if lines
+            .iter()
+            .any(|line| entry_word(line).as_ref() == Some(word))
+        {
+            println!("{word} is already in {}", path.display());
+            continue;
+        }
+
+        let mut index = lines.len();
+        if sorted {
+            let next = lines.iter().position(|line| {
+                entry_word(line)
+                    .is_some_and(|entry| compare_words(&entry, word) == Ordering::Greater)
+            });
+            if let Some(next) = next {
+                // comments above an entry stay with it
+                index = next;
+                while index > 0 && is_comment(&lines[index - 1]) {
+                    index -= 1;
+                }
+            }
+        }
+        lines.insert(index, word.to_string());
+        added += 1;
+    }
+
+    write_words(path, &lines)?;
+    println!("Added {added} word(s) to {}", path.display());
+    Ok(())
+}
+
+fn remove(words: &[String], path: &Path) -> io::Result<()> {
+    let mut lines = read_words(path)?;
+    let len = lines.len();
+    let mut found: HashSet<String> = HashSet::new();
+    lines.retain(|line| match entry_word(line) {
+        Some(entry) if words.contains(&entry) => {
+            found.insert(entry);
+            false
+        }
+        _ => true,
+    });
+
+    write_words(path, &lines)?;
+    println!(
+        "Removed {} word(s) from {}",
+        len - lines.len(),
+        path.display()
+    );
+    for word in words.iter().filter(|word| !found.contains(*word)) {
+        println!("  {word} wasn't found");
+    }
+    Ok(())
+}
+
+// lines before the first entry are kept on top, comments right above an
+// entry are moved with it and blank lines between entries are dropped
+fn sort_lines(lines: Vec<String>) -> Vec<String> {
+    let header_len = lines
+        .iter()
+        .position(|line| entry_word(line).is_some())
+        .unwrap_or(lines.len());
+    let mut sorted: Vec<String> = lines[..header_len].to_vec();
+    let mut blocks: Vec<(String, Vec<String>)> = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+
+    for line in lines.into_iter().skip(header_len) {
+        match entry_word(&line) {
+            Some(word) => {
+                comments.push(line);
+                blocks.push((word, std::mem::take(&mut comments)));
+            }
+            None if is_comment(&line) => comments.push(line),
+            None => {}
+        }
+    }
+
+    blocks.sort_by(|a, b| compare_words(&a.0, &b.0));
+    sorted.extend(blocks.into_iter().flat_map(|block| block.1));
+    sorted.extend(comments);
+    sorted
+}
+
+fn sort(path: &Path) -> io::Result<()> {
+    let lines = sort_lines(read_words(path)?);
+
+    write_words(path, &lines)?;
+    println!("Sorted {}", path.display());
+    Ok(())
+}
+
+// entries are compared without comments and flags, the first one is kept
+fn dedupe_lines(lines: &mut Vec<String>) {
+    let mut seen: HashSet<String> = HashSet::new();
+    lines.retain(|line| entry_word(line).is_none_or(|word| seen.insert(word)));
+}
+
+fn dedupe(path: &Path) -> io::Result<()> {
+    let mut lines = read_words(path)?;
+    let len = lines.len();
+    dedupe_lines(&mut lines);
+
+    write_words(path, &lines)?;
+    println!(
+        "Removed {} duplicate(s) from {}",
+        len - lines.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+pub fn lint_words(lines: &[String], ignore_list: &Trie, options: &Options) -> Vec<(usize, String)> {
+    let mut problems: Vec<(usize, String)> = Vec::new();
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+    let mut words: Vec<String> = Vec::new();
+
+    for (index, text) in lines.iter().enumerate() {
+        let line = index + 1;
+        let entry = match parse_line(text) {
+            Ok(Some(entry)) => entry,
+            Ok(None) => continue,
+            Err(error) => {
+                problems.push((line, error));
+                continue;
+            }
+        };
+        let trimmed = entry.word.as_str();
+
+        if text.trim() != text {
+            problems.push((line, format!("{trimmed}: leading or trailing whitespace")));
+        }
+        if let Some(ch) = trimmed.chars().find(|ch| !is_word_char(*ch)) {
+            problems.push((line, format!("{trimmed}: invalid character {ch:?}")));
+        }
+
+        let len = trimmed.chars().count();
+        if len < options.min.into() || len > options.max.into() {
+            problems.push((
+                line,
+                format!(
+                    "{trimmed}: length {len} is outside --min {} and --max {}",
+                    options.min, options.max
+                ),
+            ));
+        }
+        if ignore_list.search(&trimmed.to_lowercase()) {
+            problems.push((line, format!("{trimmed}: shadowed by the ignore list")));
+        }
+
+        match first_lines.get(&trimmed.to_lowercase()) {
+            Some(first) if entry_word(&lines[*first - 1]).as_deref() == Some(trimmed) => {
+                problems.push((line, format!("{trimmed}: duplicate of line {first}")));
+            }
+            Some(first) => problems.push((
+                line,
+                format!("{trimmed}: duplicate of line {first} ignoring case"),
+            )),
+            None => {
+                first_lines.insert(trimmed.to_lowercase(), line);
+            }
+        }
+        words.push(entry.word);
+    }
+
+    if !is_sorted(&words) {
+        problems.push((
+            0,
+            "entries are not sorted, run rscheck dict sort".to_string(),
+        ));
+    }
+
+    problems
+}
+
+fn lint(paths: &[String], options: &Options) -> io::Result<usize> {
+    let mut ignore_list = Trie::new();
+    for ignore in options.ignore.iter() {
+        for entry in handle_read_wordlist(ignore, None, options).map_err(io::Error::other)? {
+            ignore_list.insert(&entry.word);
+        }
+    }
+
+    let mut problems_num = 0;
+    for path in paths.iter() {
+        let words = read_words(Path::new(path))?;
+        for (line, problem) in lint_words(&words, &ignore_list, options) {
+            match line {
+                0 => println!("{path}: {problem}"),
+                _ => println!("{path}:{line}: {problem}"),
+            }
+            problems_num += 1;
+        }
+    }
+
+    println!("{problems_num} problem(s) found");
+    Ok(problems_num)
+}
+
+pub fn run(command: &DictCommand, options: &Options) {
+    let file = command.file.as_deref();
+
+    let result = match (command.action.as_str(), file) {
+        (ADD, Some(file)) => add(&command.args, file),
+        (REMOVE, Some(file)) => remove(&command.args, file),
+        (SORT, _) => command
+            .args
+            .iter()
+            .try_for_each(|path| sort(Path::new(path))),
+        (DEDUPE, _) => command
+            .args
+            .iter()
+            .try_for_each(|path| dedupe(Path::new(path))),
+        (LINT, _) => match lint(&command.args, options) {
+            Ok(0) => Ok(()),
+            Ok(_) => process::exit(1),
+            Err(error) => Err(error),
+        },
+        _ => Ok(()),
+    };
+
+    if let Err(error) = result {
+        println!("Error occurred updating wordlist: {error}");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::dict::{compare_words, dedupe_lines, lint_words, sort_lines};
+    use crate::spell_checker::options::Options;
+    use crate::trie::Trie;
+
+    #[test]
+    fn check_wordlist_lint() {
+        let mut words: Vec<String> = [
+            "apple", "Banana", "banana", "a", "cherry ", "da te", "apple",
+        ]
+        .map(str::to_string)
+        .to_vec();
+        let mut ignore_list = Trie::new();
+        ignore_list.insert("banana");
+
+        let problems = lint_words(&words, &ignore_list, &Options::default());
+        let lines: Vec<usize> = problems.iter().map(|problem| problem.0).collect();
+        assert_eq!(lines, vec![2, 3, 3, 4, 5, 6, 7, 0]);
+        assert!(problems[2].1.contains("duplicate of line 2 ignoring case"));
+        assert!(problems[3].1.contains("outside --min"));
+
+        words.sort_by(|a, b| compare_words(a, b));
+        assert_eq!(words[..5], ["a", "apple", "apple", "Banana", "banana"]);
+
+        let lines: Vec<String> = [
+            "# header",
+            "",
+            "zebra",
+            "# fruit",
+            "apple [nosuggest]",
+            "",
+            "b [x",
+        ]
+        .map(str::to_string)
+        .to_vec();
+        let problems = lint_words(&lines, &Trie::new(), &Options::default());
+        let problem_lines: Vec<usize> = problems.iter().map(|problem| problem.0).collect();
+        assert_eq!(problem_lines, vec![7, 0]);
+        assert_eq!(
+            sort_lines(lines),
+            [
+                "# header",
+                "",
+                "# fruit",
+                "apple [nosuggest]",
+                "b [x",
+                "zebra"
+            ]
+        );
+    }
+
+    #[test]
+    fn check_wordlist_dedupe() {
+        let mut lines: Vec<String> = [
+            "# words",
+            "apple",
+            "apple # again",
+            "  apple",
+            "Apple",
+            "pear [nosuggest]",
+            "pear",
+            "# words",
+        ]
+        .map(str::to_string)
+        .to_vec();
+        dedupe_lines(&mut lines);
+        assert_eq!(
+            lines,
+            ["# words", "apple", "Apple", "pear [nosuggest]", "# words"]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::trie::Trie;
+
+use super::file_utils::matches_glob;
+use super::wordlist::Entry;
+
+pub const DEFAULT_DICTIONARY: &str = "default";
+
+pub enum Lookup {
+    Found,
+    WrongCase(String),
+    Forbidden,
+    Missing,
+}
+
+// entries written in lowercase match any casing, entries with capitals have
+// to be written exactly the same way or in all caps
+pub struct Dictionary {
+    pub name: String,
+    words: Trie,
+    case_sensitive_words: HashMap<String, Vec<String>>,
+    // reported in any casing, even when another dictionary has the word
+    forbidden: Trie,
+    no_suggest: HashSet<String>,
+}
+
+impl Dictionary {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            words: Trie::new(),
+            case_sensitive_words: HashMap::new(),
+            forbidden: Trie::new(),
+            no_suggest: HashSet::new(),
+        }
+    }
+
+    pub fn insert(&mut self, word: &str) {
+        if word.chars().any(char::is_uppercase) {
+            self.case_sensitive_words
+                .entry(word.to_lowercase())
+                .or_default()
+                .push(word.to_string());
+        } else {
+            self.words.insert(word);
+        }
+    }
+
+    pub fn insert_entry(&mut self, entry: &Entry) {
+        let lowercase = entry.word.to_lowercase();
+        if entry.flags.forbidden {
+            self.forbidden.insert(&lowercase);
+            return;
+        }
+        if entry.flags.no_suggest {
+            self.no_suggest.insert(lowercase.clone());
+        }
+
+        match entry.flags.case_sensitive {
+            Some(true) => self
+                .case_sensitive_words
+                .entry(lowercase)
+                .or_default()
+                .push(entry.word.clone()),
+            Some(false) => self.words.insert(&lowercase),
+            None => self.insert(&entry.word),
+        }
+    }
+
+    pub fn lookup(&self, word: &str, case_sensitive: bool) -> Lookup {
+        let lowercase = word.to_lowercase();
+        if self.forbidden.search(&lowercase) {
+            return Lookup::Forbidden;
+        }
+        if self.words.search(&lowercase) {
+            return Lookup::Found;
+        }
+
+        match self.case_sensitive_words.get(&lowercase) {
+            Some(_) if !case_sensitive => Lookup::Found,
+            Some(forms) => {
+                if forms
+                    .iter()
+                    .any(|form| form == word || form.to_uppercase() == word)
+                {
+                    Lookup::Found
+                } else {
+                    Lookup::WrongCase(forms[0].clone())
+                }
+            }
+            None => Lookup::Missing,
+        }
+    }
+
+    fn suggest(&self, word: &str, max_distance: usize) -> Vec<(String, usize)> {
+        let lowercase = word.to_lowercase();
+        let mut suggestions = self.words.suggest(&lowercase, max_distance);
+
+        for forms in self.case_sensitive_words.values() {
+            let distance = edit_distance(&lowercase, &forms[0].to_lowercase());
+            if distance <= max_distance {
+                suggestions.push((forms[0].clone(), distance));
+            }
+        }
+
+        suggestions.retain(|(suggestion, _)| !self.no_suggest.contains(&suggestion.to_lowercase()));
+        suggestions
+    }
+
+    fn is_forbidden(&self, word: &str) -> bool {
+        self.forbidden.search(&word.to_lowercase())
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<usize>>()];
+
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (row[j - 1] + 1)
+                .min(rows[i - 1][j] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[derive(Clone)]
+pub struct DictionaryRule {
+    pub pattern: String,
+    pub dictionaries: Vec<String>,
+}
+
+pub struct Dictionaries {
+    dictionaries: Vec<Dictionary>,
+    rules: Vec<DictionaryRule>,
+}
+
+impl Dictionaries {
+    pub fn new(rules: Vec<DictionaryRule>) -> Self {
+        Self {
+            dictionaries: Vec::new(),
+            rules,
+        }
+    }
+
+    pub fn get_or_insert(&mut self, name: &str) -> &mut Dictionary {
+        let index = match self.dictionaries.iter().position(|dict| dict.name == name) {
+            Some(index) => index,
+            None => {
+                self.dictionaries.push(Dictionary::new(name));
+                self.dictionaries.len() - 1
+            }
+        };
+
+        &mut self.dictionaries[index]
+    }
+
+    // every rule matching the path contributes its dictionaries, files not
+    // matched by any rule are checked against all loaded dictionaries
+    pub fn select(&self, path: &Path) -> Vec<&Dictionary> {
+        let matching_rules: Vec<&DictionaryRule> = self
+            .rules
+            .iter()
+            .filter(|rule| matches_glob(&rule.pattern, path))
+            .collect();
+
+        if matching_rules.is_empty() {
+            return self.dictionaries.iter().collect();
+        }
+
+        self.dictionaries
+            .iter()
+            .filter(|dict| {
+                matching_rules
+                    .iter()
+                    .any(|rule| rule.dictionaries.contains(&dict.name))
+            })
+            .collect()
+    }
+}
+
+// a forbidden entry wins over the word being found in another dictionary
+pub fn lookup(dictionaries: &[&Dictionary], word: &str, case_sensitive: bool) -> Lookup {
+    if dictionaries
+        .iter()
+        .any(|dictionary| dictionary.is_forbidden(word))
+    {
+        return Lookup::Forbidden;
+    }
+    let mut result = Lookup::Missing;
+
+    for dictionary in dictionaries {
+        match dictionary.lookup(word, case_sensitive) {
+            Lookup::Found => return Lookup::Found,
+            Lookup::WrongCase(expected) => {
+                if let Lookup::Missing = result {
+                    result = Lookup::WrongCase(expected);
+                }
+            }
+            Lookup::Forbidden | Lookup::Missing => {}
+        }
+    }
+
+    result
+}
+
+// closest words first, a capitalized word gets capitalized suggestions
+pub fn suggest(dictionaries: &[&Dictionary], word: &str, limit: usize) -> Vec<String> {
+    let max_distance = if word.chars().count() <= 4 { 1 } else { 2 };
+    let mut suggestions: Vec<(String, usize)> = dictionaries
+        .iter()
+        .flat_map(|dictionary| dictionary.suggest(word, max_distance))
+        .collect();
+    suggestions.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+    let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+    let mut result: Vec<String> = Vec::new();
+
+    for (suggestion, _) in suggestions {
+        let suggestion = match suggestion.chars().next() {
+            Some(first) if capitalized && first.is_lowercase() => first
+                .to_uppercase()
+                .chain(suggestion.chars().skip(1))
+                .collect(),
+            _ => suggestion,
+        };
+
+        if !result.contains(&suggestion) {
+            result.push(suggestion);
+        }
+        if result.len() == limit {
+            break;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::dictionary::{lookup, Dictionaries, Lookup};
+    use crate::spell_checker::wordlist::parse_line;
+
+    #[test]
+    fn check_case_sensitive_lookup() {
+        let mut dictionaries = Dictionaries::new(Vec::new());
+        let words = dictionaries.get_or_insert("words");
+        for line in [
+            "JavaScript",
+            "GitHub",
+            "colour",
+            "npm [case]",
+            "Rust [nocase]",
+        ] {
+            words.insert_entry(&parse_line(line).unwrap().unwrap());
+        }
+
+        let words = dictionaries.get_or_insert("words");
+        assert!(matches!(words.lookup("JavaScript", true), Lookup::Found));
+        assert!(matches!(words.lookup("JAVASCRIPT", true), Lookup::Found));
+        assert!(
+            matches!(words.lookup("Javascript", true), Lookup::WrongCase(expected) if expected == "JavaScript")
+        );
+        assert!(
+            matches!(words.lookup("github", true), Lookup::WrongCase(expected) if expected == "GitHub")
+        );
+        // identifier words are compared case insensitively
+        assert!(matches!(words.lookup("github", false), Lookup::Found));
+        assert!(matches!(words.lookup("Colour", true), Lookup::Found));
+        assert!(matches!(words.lookup("COLOUR", true), Lookup::Found));
+        assert!(matches!(words.lookup("NPM", true), Lookup::Found));
+        assert!(matches!(words.lookup("Npm", true), Lookup::WrongCase(_)));
+        assert!(matches!(words.lookup("rust", true), Lookup::Found));
+        assert!(matches!(words.lookup("gitlab", true), Lookup::Missing));
+
+        let style = dictionaries.get_or_insert("style");
+        style.insert_entry(&parse_line("colour [forbidden]").unwrap().unwrap());
+        let selected: Vec<_> = dictionaries.select("notes.md".as_ref());
+        assert!(matches!(
+            lookup(&selected, "Colour", true),
+            Lookup::Forbidden
+        ));
+        assert!(matches!(
+            lookup(&selected, "Javascript", true),
+            Lookup::WrongCase(_)
+        ));
+        assert!(matches!(lookup(&selected, "GITHUB", true), Lookup::Found));
+    }
+}
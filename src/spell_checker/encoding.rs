@@ -0,0 +1,254 @@
+use std::fmt;
+use std::path::Path;
+
+use super::file_utils::matches_glob;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+    Latin1,
+}
+
+#[derive(Clone)]
+pub struct EncodingRule {
+    pub pattern: String,
+    pub encoding: Encoding,
+}
+
+pub struct DecodedText {
+    pub text: String,
+    pub encoding: Encoding,
+    pub bom: bool,
+    // line of the first byte sequence which couldn't be decoded
+    pub invalid_line: Option<usize>,
+}
+
+// chars of windows-1252 in the 0x80..0xA0 range, the rest matches latin1
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16_LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16_BE_BOM: &[u8] = b"\xFE\xFF";
+
+impl Encoding {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Some(Encoding::Utf8),
+            "utf-16le" | "utf-16" | "utf16le" | "utf16" => Some(Encoding::Utf16Le),
+            "utf-16be" | "utf16be" => Some(Encoding::Utf16Be),
+            "windows-1252" | "cp1252" => Some(Encoding::Windows1252),
+            "latin1" | "latin-1" | "iso-8859-1" => Some(Encoding::Latin1),
+            _ => None,
+        }
+    }
+
+    pub fn is_utf16(&self) -> bool {
+        matches!(self, Encoding::Utf16Le | Encoding::Utf16Be)
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Windows1252 => "Windows-1252",
+            Encoding::Latin1 => "Latin-1",
+        };
+        write!(f, "{name}")
+    }
+}
+
+pub fn detect_bom(content: &[u8]) -> Option<Encoding> {
+    if content.starts_with(UTF8_BOM) {
+        Some(Encoding::Utf8)
+    } else if content.starts_with(UTF16_LE_BOM) {
+        Some(Encoding::Utf16Le)
+    } else if content.starts_with(UTF16_BE_BOM) {
+        Some(Encoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+// a byte order mark wins over encodings given in options, the first
+// matching rule wins over the default encoding
+pub fn select_encoding(
+    content: &[u8],
+    path: &Path,
+    rules: &[EncodingRule],
+    default: Encoding,
+) -> Encoding {
+    if let Some(encoding) = detect_bom(content) {
+        return encoding;
+    }
+
+    rules
+        .iter()
+        .find(|rule| matches_glob(&rule.pattern, path))
+        .map_or(default, |rule| rule.encoding)
+}
+
+fn line_at(text: &str) -> usize {
+    text.matches('\n').count() + 1
+}
+
+fn decode_utf8(content: &[u8]) -> (String, Option<usize>) {
+    match String::from_utf8(content.to_vec()) {
+        Ok(text) => (text, None),
+        Err(error) => {
+            let valid = &content[..error.utf8_error().valid_up_to()];
+            let invalid_line = valid.iter().filter(|byte| **byte == b'\n').count() + 1;
+            (
+                String::from_utf8_lossy(content).into_owned(),
+                Some(invalid_line),
+            )
+        }
+    }
+}
+
+fn decode_utf16(content: &[u8], little_endian: bool) -> (String, Option<usize>) {
+    let units = content.chunks(2).map(|pair| match (pair, little_endian) {
+        ([low, high], true) => u16::from_le_bytes([*low, *high]),
+        ([high, low], false) => u16::from_be_bytes([*high, *low]),
+        // odd trailing byte
+        _ => 0xD800,
+    });
+
+    let mut text = String::new();
+    let mut invalid_line = None;
+
+    for unit in char::decode_utf16(units) {
+        match unit {
+            Ok(ch) => text.push(ch),
+            Err(_) => {
+                invalid_line.get_or_insert_with(|| line_at(&text));
+                text.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+    }
+
+    (text, invalid_line)
+}
+
+fn decode_single_byte(content: &[u8], encoding: Encoding) -> String {
+    content
+        .iter()
+        .map(|byte| match (encoding, byte) {
+            (Encoding::Windows1252, 0x80..=0x9F) => WINDOWS_1252[(*byte - 0x80) as usize],
+            _ => char::from(*byte),
+        })
+        .collect()
+}
+
+pub fn decode(content: &[u8], encoding: Encoding) -> DecodedText {
+    let bom = detect_bom(content) == Some(encoding);
+    let content = match (bom, encoding) {
+        (true, Encoding::Utf8) => &content[UTF8_BOM.len()..],
+        (true, _) => &content[UTF16_LE_BOM.len()..],
+        (false, _) => content,
+    };
+
+    let (text, invalid_line) = match encoding {
+        Encoding::Utf8 => decode_utf8(content),
+        Encoding::Utf16Le => decode_utf16(content, true),
+        Encoding::Utf16Be => decode_utf16(content, false),
+        Encoding::Windows1252 | Encoding::Latin1 => (decode_single_byte(content, encoding), None),
+    };
+
+    DecodedText {
+        text,
+        encoding,
+        bom,
+        invalid_line,
+    }
+}
+
+fn encode_single_byte(ch: char, encoding: Encoding) -> Option<u8> {
+    if encoding == Encoding::Windows1252 {
+        if let Some(index) = WINDOWS_1252.iter().position(|mapped| *mapped == ch) {
+            return Some(0x80 + index as u8);
+        }
+        if ('\u{80}'..'\u{A0}').contains(&ch) {
+            return None;
+        }
+    }
+
+    u8::try_from(u32::from(ch)).ok()
+}
+
+// returns None when the text has chars the encoding can't represent
+pub fn encode(text: &str, encoding: Encoding, bom: bool) -> Option<Vec<u8>> {
+    let mut content: Vec<u8> = Vec::new();
+
+    match encoding {
+        Encoding::Utf8 => {
+            if bom {
+                content.extend_from_slice(UTF8_BOM);
+            }
+            content.extend_from_slice(text.as_bytes());
+        }
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            let little_endian = encoding == Encoding::Utf16Le;
+            if bom {
+                content.extend_from_slice(if little_endian {
+                    UTF16_LE_BOM
+                } else {
+                    UTF16_BE_BOM
+                });
+            }
+            for unit in text.encode_utf16() {
+                if little_endian {
+                    content.extend_from_slice(&unit.to_le_bytes());
+                } else {
+                    content.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+        }
+        Encoding::Windows1252 | Encoding::Latin1 => {
+            for ch in text.chars() {
+                content.push(encode_single_byte(ch, encoding)?);
+            }
+        }
+    }
+
+    Some(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::encoding::{decode, encode, Encoding};
+
+    #[test]
+    fn check_decoding() {
+        let utf16 = b"\xFF\xFEh\x00i\x00\n\x00\xE9\x00";
+        let decoded = decode(utf16, Encoding::Utf16Le);
+        assert_eq!(decoded.text, "hi\n\u{e9}");
+        assert!(decoded.bom);
+        assert_eq!(decoded.invalid_line, None);
+        assert_eq!(
+            encode(&decoded.text, Encoding::Utf16Le, true).unwrap(),
+            utf16
+        );
+
+        let decoded = decode(b"\x93caf\xE9\x94", Encoding::Windows1252);
+        assert_eq!(decoded.text, "\u{201C}caf\u{e9}\u{201D}");
+        assert_eq!(
+            encode(&decoded.text, Encoding::Windows1252, false).unwrap(),
+            b"\x93caf\xE9\x94"
+        );
+
+        let decoded = decode(b"\xEF\xBB\xBFok\n\xFF", Encoding::Utf8);
+        assert_eq!(decoded.text, "ok\n\u{FFFD}");
+        assert_eq!(decoded.invalid_line, Some(2));
+    }
+}
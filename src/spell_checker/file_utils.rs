@@ -1,42 +1,34 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::path::Path;
 use walkdir::WalkDir;
 
-fn is_char_valid(ch: &char) -> bool {
-    ch.is_ascii_alphanumeric() || *ch == '_'
-}
+const SNIFF_LENGTH: usize = 8192;
 
-pub fn get_words_from_line(line: &str) -> Vec<String> {
-    let mut word_vec: Vec<String> = Vec::new();
-    let mut current_word = String::new();
-    let mut in_word = false;
-    let mut has_letter = false;
-
-    for ch in line.chars() {
-        if is_char_valid(&ch) {
-            current_word.push(ch);
-            in_word = true;
-            if ch.is_alphabetic() {
-                has_letter = true;
-            }
-        } else {
-            if has_letter {
-                word_vec.push(current_word.clone());
-            }
-            current_word.clear();
-            in_word = false;
-            has_letter = false;
-        }
-    }
+const MAGIC_NUMBERS: [&[u8]; 17] = [
+    b"\x89PNG",
+    b"\xFF\xD8\xFF",
+    b"GIF87a",
+    b"GIF89a",
+    b"%PDF-",
+    b"PK\x03\x04",
+    b"\x1F\x8B",
+    b"BZh",
+    b"\xFD7zXZ\x00",
+    b"7z\xBC\xAF\x27\x1C",
+    b"Rar!\x1A\x07",
+    b"\x7FELF",
+    b"\xCA\xFE\xBA\xBE",
+    b"\xCF\xFA\xED\xFE",
+    b"\x00asm",
+    b"MZ\x90\x00",
+    b"SQLite format 3\x00",
+];
 
-    if in_word {
-        word_vec.push(current_word.clone());
+pub fn is_binary(content: &[u8]) -> bool {
+    if MAGIC_NUMBERS.iter().any(|magic| content.starts_with(magic)) {
+        return true;
     }
 
-    word_vec
+    content[..content.len().min(SNIFF_LENGTH)].contains(&0)
 }
 
 pub fn get_files(path: &Path) -> WalkDir {
@@ -45,7 +37,112 @@ pub fn get_files(path: &Path) -> WalkDir {
     walk_dir
 }
 
-pub fn read_lines(filename: &Path) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+fn matches_segment(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => (0..=text.len()).any(|i| matches_segment(&pattern[1..], &text[i..])),
+        Some('?') => !text.is_empty() && matches_segment(&pattern[1..], &text[1..]),
+        Some(ch) => text.first() == Some(ch) && matches_segment(&pattern[1..], &text[1..]),
+    }
+}
+
+fn matches_segments(pattern: &[&str], components: &[&str]) -> bool {
+    match pattern.first() {
+        None => components.is_empty(),
+        Some(&"**") => {
+            (0..=components.len()).any(|i| matches_segments(&pattern[1..], &components[i..]))
+        }
+        Some(segment) => {
+            let segment: Vec<char> = segment.chars().collect();
+            match components.first() {
+                Some(component) => {
+                    let component: Vec<char> = component.chars().collect();
+                    matches_segment(&segment, &component)
+                        && matches_segments(&pattern[1..], &components[1..])
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+// patterns without a slash match the file name, other patterns match the
+// path or any of its trailing parts unless they start with a slash
+pub fn matches_glob(pattern: &str, path: &Path) -> bool {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let components: Vec<&str> = path
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+
+    let mut pattern = pattern.trim_start_matches("./").to_string();
+    if pattern.ends_with('/') {
+        pattern.push_str("**");
+    }
+
+    if !pattern.contains('/') {
+        let pattern: Vec<char> = pattern.chars().collect();
+        return match components.last() {
+            Some(file_name) => {
+                let file_name: Vec<char> = file_name.chars().collect();
+                matches_segment(&pattern, &file_name)
+            }
+            None => false,
+        };
+    }
+
+    let anchored = pattern.starts_with('/');
+    let segments: Vec<&str> = pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    if anchored {
+        return matches_segments(&segments, &components);
+    }
+
+    (0..components.len()).any(|i| matches_segments(&segments, &components[i..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::spell_checker::file_utils::{is_binary, matches_glob, SNIFF_LENGTH};
+
+    #[test]
+    fn check_glob_matching() {
+        assert!(matches_glob("*.md", Path::new("docs/de/index.md")));
+        assert!(!matches_glob("*.md", Path::new("docs/de/index.rs")));
+        assert!(matches_glob("docs/de/**", Path::new("./docs/de/index.md")));
+        assert!(matches_glob(
+            "docs/de/",
+            Path::new("project/docs/de/a/b.md")
+        ));
+        assert!(!matches_glob("docs/de/**", Path::new("docs/en/index.md")));
+        assert!(matches_glob(
+            "docs/*/index.md",
+            Path::new("docs/en/index.md")
+        ));
+        assert!(!matches_glob("/docs/**", Path::new("project/docs/a.md")));
+        assert!(matches_glob("src/**/*.rs", Path::new("src/main.rs")));
+        assert!(matches_glob("file?.txt", Path::new("file1.txt")));
+    }
+
+    #[test]
+    fn check_binary_detection() {
+        assert!(is_binary(b"\x89PNG\r\n\x1A\n"));
+        assert!(is_binary(b"%PDF-1.7\n"));
+        assert!(is_binary(b"PK\x03\x04zipped"));
+        assert!(!is_binary(b"PDF files and PNG images"));
+        assert!(!is_binary(b""));
+
+        assert!(is_binary(b"text\x00with a NUL"));
+        let mut late_nul = vec![b'a'; SNIFF_LENGTH];
+        late_nul.push(0);
+        assert!(!is_binary(&late_nul));
+
+        // utf-16 text has NUL bytes, callers skip sniffing for it
+        assert!(is_binary(&[b'h', 0, b'i', 0]));
+    }
 }
@@ -0,0 +1,66 @@
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::encoding::{encode, DecodedText};
+use super::typo::{Fix, Position};
+
+fn byte_offset(line_starts: &[usize], content: &str, position: &Position) -> Option<usize> {
+    let line_start = *line_starts.get(position.line - 1)?;
+    let line = &content[line_start..];
+
+    match line.char_indices().nth(position.column - 1) {
+        Some((offset, _)) => Some(line_start + offset),
+        None => Some(content.len()),
+    }
+}
+
+// fixes are applied from the end of the text, fixes overlapping an already
+// applied one are skipped
+pub fn apply_fixes(content: &str, fixes: &[&Fix]) -> (String, usize) {
+    let mut line_starts = vec![0];
+    line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+
+    let mut ranges: Vec<(usize, usize, &str)> = fixes
+        .iter()
+        .filter_map(|fix| {
+            let start = byte_offset(&line_starts, content, &fix.start)?;
+            let end = byte_offset(&line_starts, content, &fix.end)?;
+            (start <= end).then_some((start, end, fix.replacement.as_str()))
+        })
+        .collect();
+    ranges.sort_by_key(|range| Reverse(range.0));
+
+    let mut result = content.to_string();
+    let mut applied = 0;
+    let mut applied_start = usize::MAX;
+
+    for (start, end, replacement) in ranges {
+        if end > applied_start {
+            continue;
+        }
+        result.replace_range(start..end, replacement);
+        applied_start = start;
+        applied += 1;
+    }
+
+    (result, applied)
+}
+
+// the fixed text is written back in the encoding the file was read with,
+// nothing is written if the encoding can't represent the replacements
+pub fn fix_file(path: &Path, decoded: &DecodedText, fixes: &[&Fix]) -> io::Result<usize> {
+    let (fixed, applied) = apply_fixes(&decoded.text, fixes);
+    if applied == 0 {
+        return Ok(0);
+    }
+
+    match encode(&fixed, decoded.encoding, decoded.bom) {
+        Some(content) => {
+            fs::write(path, content)?;
+            Ok(applied)
+        }
+        None => Ok(0),
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::fs::canonicalize;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// lines added or modified in each file of a diff, deleted files are left out
+pub struct ChangedLines {
+    root: PathBuf,
+    files: HashMap<PathBuf, Vec<Range<usize>>>,
+}
+
+pub fn run_git(root: Option<&Path>, args: &[&str]) -> io::Result<Vec<u8>> {
+    let mut command = Command::new("git");
+    if let Some(root) = root {
+        command.current_dir(root);
+    }
+    let output = command.args(args).output()?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            error.trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+fn repository_root() -> io::Result<PathBuf> {
+    let output = run_git(None, &["rev-parse", "--show-toplevel"])?;
+    canonicalize(String::from_utf8_lossy(&output).trim())
+}
+
+fn parse_path(line: &str) -> Option<PathBuf> {
+    // git appends a tab to names with spaces and quotes unusual names
+    let path = line.trim_end_matches('\t').trim_matches('"');
+    path.strip_prefix("b/").map(PathBuf::from)
+}
+
+// new side of a hunk header like @@ -12,3 +14,2 @@
+fn parse_hunk(line: &str) -> Option<Range<usize>> {
+    let new_side = line.split(' ').find(|part| part.starts_with('+'))?;
+    let new_side = &new_side[1..];
+
+    let (start, len) = match new_side.split_once(',') {
+        Some((start, len)) => (start.parse().ok()?, len.parse().ok()?),
+        None => (new_side.parse().ok()?, 1),
+    };
+
+    Some(start..start + len)
+}
+
+fn parse_diff(diff: &str) -> HashMap<PathBuf, Vec<Range<usize>>> {
+    let mut files: HashMap<PathBuf, Vec<Range<usize>>> = HashMap::new();
+    let mut current: Option<PathBuf> = None;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = parse_path(path);
+        } else if line.starts_with("@@ ") {
+            let (Some(path), Some(range)) = (&current, parse_hunk(line)) else {
+                continue;
+            };
+            if !range.is_empty() {
+                files.entry(path.clone()).or_default().push(range);
+            }
+        }
+    }
+
+    files
+}
+
+impl ChangedLines {
+    // without a base the staged changes are compared to HEAD, otherwise
+    // the working tree or the staged changes are compared to the base
+    pub fn new(base: Option<&str>, staged: bool) -> io::Result<Self> {
+        let root = repository_root()?;
+
+        let mut args = vec![
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+        ];
+        if staged {
+            args.push("--cached");
+        }
+        if let Some(base) = base {
+            args.push(base);
+        }
+        args.push("--");
+
+        let diff = run_git(Some(&root), &args)?;
+        let files = parse_diff(&String::from_utf8_lossy(&diff))
+            .into_iter()
+            .map(|(path, ranges)| (root.join(path), ranges))
+            .collect();
+
+        Ok(Self { root, files })
+    }
+
+    pub fn lines(&self, path: &Path) -> Option<&[Range<usize>]> {
+        let path = canonicalize(path).ok()?;
+        self.files.get(&path).map(Vec::as_slice)
+    }
+
+    // content of the file in the index, line numbers of staged changes
+    // refer to it rather than to the working tree
+    pub fn staged_content(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let path = canonicalize(path)?;
+        let relative = path.strip_prefix(&self.root).map_err(io::Error::other)?;
+        let spec = format!(":{}", relative.to_string_lossy());
+
+        run_git(Some(&self.root), &["show", &spec])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::spell_checker::git_diff::parse_diff;
+
+    #[test]
+    fn check_diff_parsing() {
+        let diff = "diff --git a/src/main.rs b/src/main.rs
+index 1234567..89abcde 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -3 +3 @@ fn main() {
+-old
++new
+@@ -10,0 +11,2 @@
++added
++lines
+@@ -20,2 +21,0 @@
+-removed
+-lines
+diff --git a/my notes.md b/my notes.md
+--- a/my notes.md\t
++++ b/my notes.md\t
+@@ -1,0 +1,3 @@
+diff --git a/gone.txt b/gone.txt
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+";
+
+        let files = parse_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[&PathBuf::from("src/main.rs")], vec![3..4, 11..13]);
+        assert_eq!(files[&PathBuf::from("my notes.md")], vec![1..4]);
+    }
+}
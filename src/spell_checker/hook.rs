@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::git_diff::run_git;
+
+pub const PRE_COMMIT: &str = "pre-commit";
+pub const COMMIT_MSG: &str = "commit-msg";
+
+// hooks containing the marker were written by rscheck and can be replaced
+const MARKER: &str = "# installed by rscheck hook install";
+
+pub struct HookCommand {
+    pub kind: String,
+    // passed on to rscheck in the hook, e.g. wordlists
+    pub args: Vec<String>,
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "-_./=:,+@".contains(ch));
+    match safe {
+        true => arg.to_string(),
+        false => format!("'{}'", arg.replace('\'', "'\\''")),
+    }
+}
+
+// argv of the rscheck call in the hook, - stands for the checked files
+pub fn hook_argv(kind: &str, args: &[String]) -> Option<Vec<String>> {
+    let command: &[&str] = match kind {
+        PRE_COMMIT => &["--staged"],
+        COMMIT_MSG => &["commit-msg", "-"],
+        _ => return None,
+    };
+
+    Some(
+        ["rscheck"]
+            .iter()
+            .chain(command)
+            .map(|arg| arg.to_string())
+            .chain(args.iter().cloned())
+            .chain((kind == PRE_COMMIT).then(|| "-".to_string()))
+            .collect(),
+    )
+}
+
+fn hook_script(kind: &str, args: &[String]) -> Option<String> {
+    let args: String = args
+        .iter()
+        .map(|arg| format!(" {}", shell_quote(arg)))
+        .collect();
+    // only staged files are passed, their lines are read from the index and
+    // directories like .git or target are never walked
+    let command = match kind {
+        PRE_COMMIT => format!(
+            "git diff --cached --name-only --diff-filter=d -z | xargs -0 -r rscheck --staged{args}"
+        ),
+        COMMIT_MSG => format!("exec rscheck commit-msg \"$1\"{args}"),
+        _ => return None,
+    };
+
+    Some(format!("#!/bin/sh\n{MARKER}\n{command}\n"))
+}
+
+fn hooks_dir() -> Result<PathBuf, String> {
+    let output = run_git(None, &["rev-parse", "--git-path", "hooks"])
+        .map_err(|error| format!("Error occurred finding git hooks: {error}"))?;
+
+    Ok(PathBuf::from(String::from_utf8_lossy(&output).trim()))
+}
+
+fn write_hook(dir: &Path, hook: &HookCommand, force: bool) -> Result<PathBuf, String> {
+    let kind = &hook.kind;
+    let script = hook_script(kind, &hook.args)
+        .ok_or_else(|| format!("unknown hook {kind}, use {PRE_COMMIT} or {COMMIT_MSG}"))?;
+    let path = dir.join(kind);
+
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !force && !existing.contains(MARKER) {
+            return Err(format!(
+                "{} already exists, pass --force to replace it",
+                path.display()
+            ));
+        }
+    }
+
+    let write = || -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(&path, script)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(())
+    };
+    write().map_err(|error| format!("Error occurred writing {}: {error}", path.display()))?;
+
+    Ok(path)
+}
+
+pub fn install_hook(hook: &HookCommand, force: bool) -> Result<PathBuf, String> {
+    write_hook(&hooks_dir()?, hook, force)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::spell_checker::hook::{
+        hook_script, write_hook, HookCommand, COMMIT_MSG, MARKER, PRE_COMMIT,
+    };
+
+    #[test]
+    fn check_hook_scripts() {
+        let args = ["-w".to_string(), "my words.txt".to_string()];
+        let script = hook_script(PRE_COMMIT, &args).unwrap();
+        assert!(script.starts_with(&format!("#!/bin/sh\n{MARKER}\n")));
+        assert!(script.contains("--name-only"));
+        assert!(script.ends_with("rscheck --staged -w 'my words.txt'\n"));
+        assert!(!script.contains(" -t ."));
+        assert!(hook_script(COMMIT_MSG, &[])
+            .unwrap()
+            .ends_with("exec rscheck commit-msg \"$1\"\n"));
+        assert!(hook_script("post-merge", &[]).is_none());
+
+        let dir = std::env::temp_dir().join(format!("rscheck-hooks-{}", std::process::id()));
+        let hook = HookCommand {
+            kind: PRE_COMMIT.to_string(),
+            args: Vec::new(),
+        };
+        let path = write_hook(&dir, &hook, false).unwrap();
+        // hooks written by rscheck are replaced without --force
+        assert!(write_hook(&dir, &hook, false).is_ok());
+
+        fs::write(&path, "#!/bin/sh\nmake lint\n").unwrap();
+        assert!(write_hook(&dir, &hook, false).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\nmake lint\n");
+        write_hook(&dir, &hook, true).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains(MARKER));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::cache::is_cache_path;
+use super::encoding::{decode, select_encoding};
+use super::file_utils::{get_files, is_binary};
+use super::options::{Options, STDIN_TARGET};
+use super::report::note;
+use super::stats::CheckStats;
+use super::typo::{Typo, TypoKind};
+use super::wordlist::wordlist_entry;
+use super::{skip_file, Checker};
+
+pub struct LearnCommand {
+    pub output: Option<PathBuf>,
+    // words found fewer times are left out
+    pub min_count: u16,
+}
+
+#[derive(Default)]
+struct Candidate {
+    count: usize,
+    files: usize,
+    forms: HashMap<String, usize>,
+}
+
+impl Candidate {
+    // the most common form becomes the entry, ties go to the first form in
+    // byte order
+    fn entry(&self, word: &str) -> String {
+        let form = self
+            .forms
+            .iter()
+            .max_by_key(|(form, count)| (**count, Reverse(form.as_str())))
+            .map_or(word, |(form, _)| form.as_str());
+
+        wordlist_entry(form)
+    }
+}
+
+fn target_files(options: &Options) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for target in options.targets.iter() {
+        if target == Path::new(STDIN_TARGET) {
+            continue;
+        }
+        if target.is_file() {
+            if !skip_file(target, options)? {
+                files.push(target.to_path_buf());
+            }
+            continue;
+        }
+
+        for file in get_files(target).into_iter().filter_map(|x| x.ok()) {
+            let file = file.path();
+            if file.is_file() && !is_cache_path(file) && !skip_file(file, options)? {
+                files.push(file.to_path_buf());
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+// misspelled words of one file
+fn add_candidates(typos: Vec<Typo>, candidates: &mut HashMap<String, Candidate>) {
+    let mut words_in_file: Vec<String> = Vec::new();
+
+    for typo in typos {
+        if !matches!(typo.kind, TypoKind::Misspelled) {
+            continue;
+        }
+
+        let key = typo.word.to_lowercase();
+        let candidate = candidates.entry(key.clone()).or_default();
+        candidate.count += 1;
+        *candidate.forms.entry(typo.word).or_default() += 1;
+
+        if !words_in_file.contains(&key) {
+            candidate.files += 1;
+            words_in_file.push(key);
+        }
+    }
+}
+
+// files that can't be read are counted as errors and skipped
+fn collect_candidates(
+    checker: &Checker,
+    options: &Options,
+    stats: &mut CheckStats,
+) -> io::Result<HashMap<String, Candidate>> {
+    let mut candidates: HashMap<String, Candidate> = HashMap::new();
+
+    for path in target_files(options)? {
+        let content = match fs::read(&path) {
+            Ok(content) => content,
+            Err(error) => {
+                note(
+                    options,
+                    &format!("Error occurred reading {}: {error}", path.display()),
+                );
+                stats.errors += 1;
+                continue;
+            }
+        };
+        let encoding = select_encoding(&content, &path, &options.encoding_rules, options.encoding);
+        if !encoding.is_utf16() && is_binary(&content) {
+            continue;
+        }
+
+        let decoded = decode(&content, encoding);
+        add_candidates(checker.check_text(&decoded.text, &path), &mut candidates);
+    }
+
+    Ok(candidates)
+}
+
+// most common words first, then words found in more files
+fn rank(candidates: &HashMap<String, Candidate>, min_count: u16) -> Vec<(String, &Candidate)> {
+    let mut ranked: Vec<(String, &Candidate)> = candidates
+        .iter()
+        .filter(|(_, candidate)| candidate.count >= min_count.into())
+        .map(|(word, candidate)| (candidate.entry(word), candidate))
+        .collect();
+    ranked.sort_by(|a, b| {
+        (b.1.count, b.1.files)
+            .cmp(&(a.1.count, a.1.files))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    ranked
+}
+
+// words missing from the dictionaries ranked by occurrences and files, the
+// list is meant to be reviewed before it's used as a wordlist
+pub fn learn(
+    command: &LearnCommand,
+    checker: &Checker,
+    options: &Options,
+    stats: &mut CheckStats,
+) -> io::Result<()> {
+    let candidates = collect_candidates(checker, options, stats)?;
+    let ranked = rank(&candidates, command.min_count);
+
+    for (word, candidate) in ranked.iter() {
+        println!(
+            "  {word}: {} time(s) in {} file(s)",
+            candidate.count, candidate.files
+        );
+    }
+    println!("{} candidate word(s) found", ranked.len());
+
+    if let Some(output) = &command.output {
+        let mut content: String = ranked.iter().map(|(word, _)| format!("{word}\n")).collect();
+        if ranked.is_empty() {
+            content.clear();
+        }
+        fs::write(output, content)?;
+        println!("Candidates written to {}", output.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use crate::spell_checker::learn::{add_candidates, rank};
+    use crate::spell_checker::options::Options;
+    use crate::spell_checker::Checker;
+
+    #[test]
+    fn check_candidate_ranking() {
+        let options = Options::default();
+        let checker = Checker::new(&options).unwrap();
+        let mut candidates = HashMap::new();
+        for text in ["ZORP blarg ZORP", "Quxx ZORP quxx", "quxx zorp"] {
+            add_candidates(checker.check_text(text, Path::new("a.md")), &mut candidates);
+        }
+
+        let ranked: Vec<(String, usize, usize)> = rank(&candidates, 1)
+            .into_iter()
+            .map(|(word, candidate)| (word, candidate.count, candidate.files))
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                ("ZORP".to_string(), 4, 3),
+                ("quxx".to_string(), 3, 2),
+                ("blarg".to_string(), 1, 1),
+            ]
+        );
+
+        let words: Vec<String> = rank(&candidates, 3)
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect();
+        assert_eq!(words, ["ZORP", "quxx"]);
+    }
+}
@@ -0,0 +1,601 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use super::typo::{Fix, Position, Typo, TypoKind};
+use super::wordlist::{parse_line, wordlist_entry, Entry};
+use super::{Checker, LineCache};
+
+// words added from the editor are appended to this file in the project root
+pub const PROJECT_WORDLIST: &str = ".rscheck-words.txt";
+
+const ADD_TO_WORDLIST: &str = "rscheck.addToWordlist";
+const SOURCE: &str = "rscheck";
+
+const METHOD_NOT_FOUND: i64 = -32601;
+
+struct Document {
+    text: String,
+    cache: LineCache,
+    typos: Vec<Typo>,
+}
+
+struct Server<'a> {
+    checker: Checker<'a>,
+    documents: HashMap<String, Document>,
+    root: PathBuf,
+}
+
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(io::Error::other("message without Content-Length"));
+    };
+    let mut content = vec![0; content_length];
+    input.read_exact(&mut content)?;
+
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(io::Error::other)
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    output.flush()
+}
+
+fn uri_to_path(uri: &str) -> PathBuf {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let bytes = path.as_bytes();
+    let mut decoded: Vec<u8> = Vec::new();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+// lsp characters are counted in utf-16 code units
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+fn byte_offset(text: &str, line: usize, character: usize) -> usize {
+    let mut line_start = 0;
+    for _ in 0..line {
+        match text[line_start..].find('\n') {
+            Some(index) => line_start += index + 1,
+            None => return text.len(),
+        }
+    }
+
+    let mut units = 0;
+    for (offset, ch) in text[line_start..].char_indices() {
+        if units >= character || ch == '\n' {
+            return line_start + offset;
+        }
+        units += ch.len_utf16();
+    }
+
+    text.len()
+}
+
+fn lsp_position(lines: &[&str], position: &Position) -> Value {
+    let line = lines.get(position.line - 1).copied().unwrap_or("");
+    let prefix: String = line.chars().take(position.column - 1).collect();
+
+    json!({ "line": position.line - 1, "character": utf16_len(&prefix) })
+}
+
+fn typo_range(lines: &[&str], typo: &Typo) -> Value {
+    let start = Position {
+        line: typo.line,
+        column: typo.column,
+    };
+    let end = Position {
+        line: typo.line,
+        column: typo.column + typo.word.chars().count(),
+    };
+
+    json!({ "start": lsp_position(lines, &start), "end": lsp_position(lines, &end) })
+}
+
+fn diagnostic(lines: &[&str], typo: &Typo) -> Value {
+    let message = match &typo.kind {
+        TypoKind::Misspelled => format!("Unknown word: {}", typo.word),
+        TypoKind::WrongCase(expected) => {
+            format!("{} should be written as {expected}", typo.word)
+        }
+        TypoKind::Forbidden => format!("Forbidden word: {}", typo.word),
+        TypoKind::Style {
+            preferred: Some(preferred),
+            ..
+        } => format!("Use {preferred} instead of {}", typo.word),
+        TypoKind::Style { .. } => format!("Forbidden term: {}", typo.word),
+        TypoKind::Variant {
+            expected,
+            preferred,
+        } => format!("{expected} spelling of {} is {preferred}", typo.word),
+        TypoKind::Repeated(_) => format!("Repeated word: {}", typo.word),
+    };
+
+    let mut diagnostic = json!({
+        "range": typo_range(lines, typo),
+        "severity": 3,
+        "source": SOURCE,
+        "message": message,
+    });
+    if let TypoKind::Style { rule, .. } = &typo.kind {
+        diagnostic["code"] = json!(rule);
+    }
+
+    diagnostic
+}
+
+fn text_edit(uri: &str, range: Value, new_text: &str) -> Value {
+    json!({ "changes": { uri: [{ "range": range, "newText": new_text }] } })
+}
+
+// the range of a fix can differ from the word, e.g. it leaves out a
+// possessive 's
+fn fix_edit(uri: &str, lines: &[&str], fix: &Fix) -> Value {
+    let range = json!({
+        "start": lsp_position(lines, &fix.start),
+        "end": lsp_position(lines, &fix.end),
+    });
+    text_edit(uri, range, &fix.replacement)
+}
+
+fn position_key(position: &Value) -> (u64, u64) {
+    (
+        position["line"].as_u64().unwrap_or(0),
+        position["character"].as_u64().unwrap_or(0),
+    )
+}
+
+impl Server<'_> {
+    fn publish(&mut self, uri: &str, output: &mut impl Write) -> io::Result<()> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+
+        let path = uri_to_path(uri);
+        document.typos =
+            self.checker
+                .check_text_cached(&document.text, &path, Some(&mut document.cache));
+
+        let lines: Vec<&str> = document.text.lines().collect();
+        let diagnostics: Vec<Value> = document
+            .typos
+            .iter()
+            .map(|typo| diagnostic(&lines, typo))
+            .collect();
+
+        write_message(
+            output,
+            &json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": { "uri": uri, "diagnostics": diagnostics },
+            }),
+        )
+    }
+
+    fn did_open(&mut self, params: &Value, output: &mut impl Write) -> io::Result<()> {
+        let document = &params["textDocument"];
+        let (Some(uri), Some(text)) = (document["uri"].as_str(), document["text"].as_str()) else {
+            return Ok(());
+        };
+
+        self.documents.insert(
+            uri.to_string(),
+            Document {
+                text: text.to_string(),
+                cache: LineCache::new(),
+                typos: Vec::new(),
+            },
+        );
+        self.publish(uri, output)
+    }
+
+    // changes with a range replace part of the text, changes without one
+    // replace the whole text
+    fn did_change(&mut self, params: &Value, output: &mut impl Write) -> io::Result<()> {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Ok(());
+        };
+        let Some(document) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+
+        for change in params["contentChanges"].as_array().into_iter().flatten() {
+            let Some(text) = change["text"].as_str() else {
+                continue;
+            };
+
+            match change.get("range") {
+                Some(range) => {
+                    let (start_line, start_character) = position_key(&range["start"]);
+                    let (end_line, end_character) = position_key(&range["end"]);
+                    let start = byte_offset(
+                        &document.text,
+                        start_line as usize,
+                        start_character as usize,
+                    );
+                    let end =
+                        byte_offset(&document.text, end_line as usize, end_character as usize);
+                    document.text.replace_range(start..end.max(start), text);
+                }
+                None => document.text = text.to_string(),
+            }
+        }
+
+        self.publish(uri, output)
+    }
+
+    fn code_actions(&self, params: &Value) -> Value {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return json!([]);
+        };
+        let Some(document) = self.documents.get(uri) else {
+            return json!([]);
+        };
+
+        let path = uri_to_path(uri);
+        let lines: Vec<&str> = document.text.lines().collect();
+        let start = position_key(&params["range"]["start"]);
+        let end = position_key(&params["range"]["end"]);
+        let mut actions: Vec<Value> = Vec::new();
+
+        for typo in document.typos.iter() {
+            let range = typo_range(&lines, typo);
+            if position_key(&range["end"]) < start || position_key(&range["start"]) > end {
+                continue;
+            }
+            let diagnostics = json!([diagnostic(&lines, typo)]);
+
+            match &typo.kind {
+                TypoKind::Misspelled | TypoKind::Forbidden => {
+                    for suggestion in self.checker.suggest(&typo.word, &path) {
+                        actions.push(json!({
+                            "title": format!("Change to {suggestion}"),
+                            "kind": "quickfix",
+                            "diagnostics": diagnostics,
+                            "edit": text_edit(uri, range.clone(), &suggestion),
+                        }));
+                    }
+                    if matches!(typo.kind, TypoKind::Forbidden) {
+                        continue;
+                    }
+
+                    let word = wordlist_entry(&typo.word);
+                    actions.push(json!({
+                        "title": format!("Add {word} to project wordlist"),
+                        "kind": "quickfix",
+                        "diagnostics": diagnostics,
+                        "command": {
+                            "title": format!("Add {word} to project wordlist"),
+                            "command": ADD_TO_WORDLIST,
+                            "arguments": [word],
+                        },
+                    }));
+                }
+                TypoKind::WrongCase(expected) => actions.push(json!({
+                    "title": format!("Change to {expected}"),
+                    "kind": "quickfix",
+                    "diagnostics": diagnostics,
+                    "edit": text_edit(uri, range, expected),
+                })),
+                // like --fix, only typos with a fix can be changed, words in
+                // identifiers are left alone
+                TypoKind::Style { .. } | TypoKind::Variant { .. } => {
+                    if let Some(fix) = &typo.fix {
+                        actions.push(json!({
+                            "title": format!("Change to {}", fix.replacement),
+                            "kind": "quickfix",
+                            "diagnostics": diagnostics,
+                            "edit": fix_edit(uri, &lines, fix),
+                        }));
+                    }
+                }
+                TypoKind::Repeated(_) => {
+                    if let Some(fix) = &typo.fix {
+                        actions.push(json!({
+                            "title": format!("Remove repeated {}", typo.word),
+                            "kind": "quickfix",
+                            "diagnostics": diagnostics,
+                            "edit": fix_edit(uri, &lines, fix),
+                        }));
+                    }
+                }
+            }
+        }
+
+        Value::Array(actions)
+    }
+
+    // words added in previous sessions
+    fn load_project_wordlist(&mut self) {
+        if let Ok(words) = fs::read_to_string(self.root.join(PROJECT_WORDLIST)) {
+            for entry in words
+                .lines()
+                .filter_map(|line| parse_line(line).ok().flatten())
+            {
+                self.checker.add_word(&entry);
+            }
+        }
+    }
+
+    fn add_to_wordlist(&mut self, word: &str, output: &mut impl Write) -> io::Result<()> {
+        let path = self.root.join(PROJECT_WORDLIST);
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{word}")?;
+
+        self.checker.add_word(&Entry::new(word));
+
+        let uris: Vec<String> = self.documents.keys().cloned().collect();
+        for uri in uris {
+            // cached lines may contain the word
+            if let Some(document) = self.documents.get_mut(&uri) {
+                document.cache.clear();
+            }
+            self.publish(&uri, output)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(
+        &mut self,
+        method: &str,
+        params: &Value,
+        output: &mut impl Write,
+    ) -> io::Result<Result<Value, (i64, String)>> {
+        let result = match method {
+            "initialize" => {
+                if let Some(root) = params["rootUri"].as_str() {
+                    self.root = uri_to_path(root);
+                } else if let Some(root) = params["rootPath"].as_str() {
+                    self.root = PathBuf::from(root);
+                }
+                self.load_project_wordlist();
+
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": 2 },
+                        "codeActionProvider": true,
+                        "executeCommandProvider": { "commands": [ADD_TO_WORDLIST] },
+                    },
+                    "serverInfo": { "name": "rscheck" },
+                })
+            }
+            "shutdown" => Value::Null,
+            "textDocument/codeAction" => self.code_actions(params),
+            "workspace/executeCommand" => {
+                if params["command"].as_str() == Some(ADD_TO_WORDLIST) {
+                    if let Some(word) = params["arguments"][0].as_str() {
+                        self.add_to_wordlist(word, output)?;
+                    }
+                }
+                Value::Null
+            }
+            _ => return Ok(Err((METHOD_NOT_FOUND, format!("unknown method {method}")))),
+        };
+
+        Ok(Ok(result))
+    }
+
+    fn handle_notification(
+        &mut self,
+        method: &str,
+        params: &Value,
+        output: &mut impl Write,
+    ) -> io::Result<()> {
+        match method {
+            "textDocument/didOpen" => self.did_open(params, output),
+            "textDocument/didChange" => self.did_change(params, output),
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.documents.remove(uri);
+                    write_message(
+                        output,
+                        &json!({
+                            "jsonrpc": "2.0",
+                            "method": "textDocument/publishDiagnostics",
+                            "params": { "uri": uri, "diagnostics": [] },
+                        }),
+                    )?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// serves requests until the client sends exit or closes the input
+pub fn run(checker: Checker, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    let mut server = Server {
+        checker,
+        documents: HashMap::new(),
+        root: PathBuf::from("."),
+    };
+
+    while let Some(message) = read_message(input)? {
+        let Some(method) = message["method"].as_str() else {
+            continue;
+        };
+        if method == "exit" {
+            break;
+        }
+        let params = &message["params"];
+
+        match message.get("id") {
+            Some(id) => {
+                let response = match server.handle_request(method, params, output)? {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err((code, error)) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": code, "message": error },
+                    }),
+                };
+                write_message(output, &response)?;
+            }
+            None => server.handle_notification(method, params, output)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use serde_json::{json, Value};
+
+    use crate::spell_checker::lsp::{read_message, run, write_message};
+    use crate::spell_checker::options::Options;
+    use crate::spell_checker::Checker;
+
+    fn messages(output: &[u8]) -> Vec<Value> {
+        let mut reader = BufReader::new(output);
+        let mut messages = Vec::new();
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    #[test]
+    fn check_incremental_diagnostics() {
+        let argv = ["rscheck", "lsp"].map(str::to_string);
+        let options = Options::create(argv).unwrap();
+        let checker = Checker::new(&options).unwrap();
+
+        let uri = "file:///tmp/notes.txt";
+        let mut input: Vec<u8> = Vec::new();
+        for message in [
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+                "textDocument": { "uri": uri, "text": "hello wrold\nsecond line" },
+            }}),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+                "textDocument": { "uri": uri },
+                "contentChanges": [{
+                    "range": {
+                        "start": { "line": 0, "character": 7 },
+                        "end": { "line": 0, "character": 9 },
+                    },
+                    "text": "or",
+                }],
+            }}),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ] {
+            write_message(&mut input, &message).unwrap();
+        }
+
+        let mut output: Vec<u8> = Vec::new();
+        run(checker, &mut BufReader::new(input.as_slice()), &mut output).unwrap();
+
+        let messages = messages(&output);
+        assert_eq!(messages.len(), 2);
+        let diagnostics = &messages[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(
+            diagnostics[0]["range"]["start"],
+            json!({ "line": 0, "character": 6 })
+        );
+        assert_eq!(diagnostics[0]["message"], "Unknown word: wrold");
+        assert_eq!(messages[1]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn check_variant_code_actions() {
+        let argv = ["rscheck", "lsp", "--variant", "us"].map(str::to_string);
+        let options = Options::create(argv).unwrap();
+        let checker = Checker::new(&options).unwrap();
+
+        let uri = "file:///tmp/notes.txt";
+        let mut input: Vec<u8> = Vec::new();
+        for message in [
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+                "textDocument": { "uri": uri, "text": "the colour's shade in setColour" },
+            }}),
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "textDocument/codeAction", "params": {
+                "textDocument": { "uri": uri },
+                "range": {
+                    "start": { "line": 0, "character": 0 },
+                    "end": { "line": 0, "character": 31 },
+                },
+            }}),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ] {
+            write_message(&mut input, &message).unwrap();
+        }
+
+        let mut output: Vec<u8> = Vec::new();
+        run(checker, &mut BufReader::new(input.as_slice()), &mut output).unwrap();
+
+        let messages = messages(&output);
+        assert_eq!(
+            messages[0]["params"]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        // setColour is reported but has no action
+        let actions = messages[1]["result"].as_array().unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0]["title"], "Change to color");
+        assert_eq!(
+            actions[0]["edit"]["changes"][uri][0],
+            json!({
+                "range": {
+                    "start": { "line": 0, "character": 4 },
+                    "end": { "line": 0, "character": 10 },
+                },
+                "newText": "color",
+            })
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use regex::Regex;
+
+use super::parse_variables::split_words;
+
+const LINE_PATTERNS: [&str; 3] = [
+    // urls with a scheme or starting with www.
+    r#"(?:[A-Za-z][A-Za-z0-9+.-]*://|www\.)[^\s<>"'`)\]]+"#,
+    // email addresses
+    r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}",
+    // uuids
+    r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b",
+];
+
+const BASE64_PATTERN: &str = r"[A-Za-z0-9+/_-]{20,}={0,2}";
+
+// hex literals like 0xdeadbeef or 0xffu8
+const HEX_LITERAL_PATTERN: &str = r"^0[xX][0-9a-fA-F_]+(?:[iu](?:8|16|32|64|128|size))?$";
+
+// commit hashes and other hex digests
+const DIGEST_PATTERN: &str = r"^[0-9a-fA-F]{7,}$";
+
+pub struct NonWordFilter {
+    line_patterns: Vec<Regex>,
+    base64_pattern: Regex,
+    hex_literal_pattern: Regex,
+    digest_pattern: Regex,
+    user_patterns: Vec<Regex>,
+}
+
+fn compile(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("builtin pattern is invalid")
+}
+
+// paths and identifiers like src/Components/Header2Wrapper split into words,
+// base64 splits into pieces with single letters like a, G or Vsb
+fn is_identifier_words(candidate: &str) -> bool {
+    candidate
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .flat_map(split_words)
+        .all(|word| word.len() > 1)
+}
+
+// base64 blobs mix lowercase, uppercase and digits in a run of 20 chars, so
+// do some paths and identifiers
+fn is_base64(candidate: &str) -> bool {
+    candidate.ends_with('=')
+        || (candidate.chars().any(|ch| ch.is_ascii_lowercase())
+            && candidate.chars().any(|ch| ch.is_ascii_uppercase())
+            && candidate.chars().any(|ch| ch.is_ascii_digit())
+            && !is_identifier_words(candidate))
+}
+
+fn is_digest(token: &str) -> bool {
+    token.chars().any(|ch| ch.is_ascii_digit()) && token.chars().any(|ch| ch.is_alphabetic())
+}
+
+impl NonWordFilter {
+    pub fn new(user_patterns: &[Regex]) -> Self {
+        Self {
+            line_patterns: LINE_PATTERNS
+                .iter()
+                .map(|pattern| compile(pattern))
+                .collect(),
+            base64_pattern: compile(BASE64_PATTERN),
+            hex_literal_pattern: compile(HEX_LITERAL_PATTERN),
+            digest_pattern: compile(DIGEST_PATTERN),
+            user_patterns: user_patterns.to_vec(),
+        }
+    }
+
+    // replaces urls, emails, encoded data and matches of user patterns with
+    // spaces, so columns of the remaining words stay the same
+    pub fn mask_line(&self, line: &str) -> String {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+        for pattern in self.line_patterns.iter().chain(self.user_patterns.iter()) {
+            for found in pattern.find_iter(line) {
+                ranges.push((found.start(), found.end()));
+            }
+        }
+
+        for found in self.base64_pattern.find_iter(line) {
+            if is_base64(found.as_str()) {
+                ranges.push((found.start(), found.end()));
+            }
+        }
+
+        if ranges.is_empty() {
+            return line.to_string();
+        }
+
+        line.char_indices()
+            .map(|(i, ch)| {
+                if ranges.iter().any(|(start, end)| i >= *start && i < *end) {
+                    ' '
+                } else {
+                    ch
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_non_word(&self, token: &str) -> bool {
+        if self.hex_literal_pattern.is_match(token)
+            || (self.digest_pattern.is_match(token) && is_digest(token))
+        {
+            return true;
+        }
+
+        self.user_patterns.iter().any(|pattern| {
+            pattern
+                .find(token)
+                .is_some_and(|found| found.start() == 0 && found.end() == token.len())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::non_words::NonWordFilter;
+    use regex::Regex;
+
+    #[test]
+    fn check_line_masking() {
+        let filter = NonWordFilter::new(&[]);
+        assert_eq!(
+            filter.mask_line("see https://exmple.com/pth?q=1 now"),
+            format!("see {} now", " ".repeat(26))
+        );
+        assert_eq!(
+            filter.mask_line("mail jdoe@exmple.org"),
+            format!("mail {}", " ".repeat(15))
+        );
+        assert_eq!(
+            filter.mask_line("id 123e4567-e89b-12d3-a456-426614174000"),
+            format!("id {}", " ".repeat(36))
+        );
+        assert_eq!(
+            filter.mask_line("key aGVsbG8gd29ybGQgZm9vYmFy"),
+            format!("key {}", " ".repeat(24))
+        );
+        assert_eq!(
+            filter.mask_line("src/spell_checker/parse_variables.rs"),
+            "src/spell_checker/parse_variables.rs"
+        );
+        assert_eq!(
+            filter.mask_line("see src/Components/Header2Wrapper.tsx"),
+            "see src/Components/Header2Wrapper.tsx"
+        );
+        assert_eq!(
+            filter.mask_line("getHTTP2ResponseHeaders"),
+            "getHTTP2ResponseHeaders"
+        );
+        assert_eq!(
+            filter.mask_line("token eyJhbGciOiJIUzI1NiJ9x"),
+            format!("token {}", " ".repeat(21))
+        );
+    }
+
+    #[test]
+    fn check_non_word_tokens() {
+        let filter = NonWordFilter::new(&[Regex::new("TODO[0-9]+").unwrap()]);
+        assert!(filter.is_non_word("0xdeadbeef"));
+        assert!(filter.is_non_word("3f2a9c1"));
+        assert!(filter.is_non_word("TODO12"));
+        assert!(!filter.is_non_word("deadbeef"));
+        assert!(!filter.is_non_word("decade"));
+        assert!(!filter.is_non_word("xTODO12"));
+    }
+}
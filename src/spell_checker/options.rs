@@ -1,12 +1,68 @@
-use std::{collections::HashMap, env::Args, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use super::builtin::{self, is_builtin};
+use super::dict::{self, DictCommand};
+use super::dictionary::{DictionaryRule, DEFAULT_DICTIONARY};
+use super::encoding::{Encoding, EncodingRule};
+use super::hook::{self, HookCommand};
+use super::learn::LearnCommand;
+use super::report::{Format, GROUP_BY_WORD};
+use super::variants::VariantMode;
+
+// target reading the text to check from stdin
+pub const STDIN_TARGET: &str = "-";
+
+pub const COMMIT_MSG_COMMAND: &str = "commit-msg";
+pub const HOOK_COMMAND: &str = "hook";
+pub const LSP_COMMAND: &str = "lsp";
+pub const DICT_COMMAND: &str = "dict";
+pub const LEARN_COMMAND: &str = "learn";
+
+pub struct NamedWordlist {
+    pub name: String,
+    pub path: PathBuf,
+}
 
 pub struct Options {
     pub targets: Vec<PathBuf>,
-    pub wordlists: Vec<PathBuf>,
+    pub stdin_filename: Option<PathBuf>,
+    pub wordlists: Vec<NamedWordlist>,
+    pub builtin_dictionaries: Vec<String>,
+    pub dictionary_rules: Vec<DictionaryRule>,
     pub ignore: Vec<PathBuf>,
+    pub style_rules: Vec<PathBuf>,
     pub paths_to_exclude: Vec<PathBuf>,
     pub extensions: Vec<String>,
     pub extensions_to_exclude: Vec<String>,
+    pub ignore_regexes: Vec<Regex>,
+    pub variant: Option<VariantMode>,
+    pub format: Format,
+    // extended summary with the top entries of each list
+    pub stats: bool,
+    pub top: u16,
+    pub group_by_word: bool,
+    pub unique: bool,
+    // source lines shown before and after a typo
+    pub context: u16,
+    pub encoding: Encoding,
+    pub encoding_rules: Vec<EncodingRule>,
+    pub repeated_words: bool,
+    pub fix: bool,
+    pub diff_base: Option<String>,
+    pub staged: bool,
+    pub commit_msg: bool,
+    pub hook: Option<HookCommand>,
+    pub force: bool,
+    pub lsp: bool,
+    pub watch: bool,
+    pub cache: bool,
+    pub dict: Option<DictCommand>,
+    pub learn: Option<LearnCommand>,
     pub min: u16,
     pub max: u16,
 }
@@ -26,30 +82,79 @@ impl Arg {
         match str {
             "--min" => Ok(Arg::Single("--min")),
             "--max" => Ok(Arg::Single("--max")),
-            "-t" => Ok(Arg::Pair(ArgsPair {
+            "--fix" => Ok(Arg::Single("--fix")),
+            "--stdin-filename" => Ok(Arg::Single("--stdin-filename")),
+            "--diff-base" => Ok(Arg::Single("--diff-base")),
+            "--staged" => Ok(Arg::Single("--staged")),
+            "--force" => Ok(Arg::Single("--force")),
+            "--watch" => Ok(Arg::Single("--watch")),
+            "--no-cache" => Ok(Arg::Single("--no-cache")),
+            "--to" => Ok(Arg::Single("--to")),
+            "--from" => Ok(Arg::Single("--from")),
+            "--min-count" => Ok(Arg::Single("--min-count")),
+            "--variant" => Ok(Arg::Single("--variant")),
+            "--format" => Ok(Arg::Single("--format")),
+            "--stats" => Ok(Arg::Single("--stats")),
+            "--top" => Ok(Arg::Single("--top")),
+            "--group-by" => Ok(Arg::Single("--group-by")),
+            "--unique" => Ok(Arg::Single("--unique")),
+            "--context" => Ok(Arg::Single("--context")),
+            "-t" | "--target" => Ok(Arg::Pair(ArgsPair {
                 long: "--target",
                 short: "-t",
             })),
-            "-w" => Ok(Arg::Pair(ArgsPair {
+            "-w" | "--wordlist" => Ok(Arg::Pair(ArgsPair {
                 long: "--wordlist",
                 short: "-w",
             })),
-            "-i" => Ok(Arg::Pair(ArgsPair {
+            "-i" | "--ignore" => Ok(Arg::Pair(ArgsPair {
                 long: "--ignore",
                 short: "-i",
             })),
-            "-e" => Ok(Arg::Pair(ArgsPair {
+            "-sr" | "--style-rules" => Ok(Arg::Pair(ArgsPair {
+                long: "--style-rules",
+                short: "-sr",
+            })),
+            "-e" | "--extension" => Ok(Arg::Pair(ArgsPair {
                 long: "--extension",
                 short: "-e",
             })),
-            "-xe" => Ok(Arg::Pair(ArgsPair {
+            "-xe" | "--exclude-extension" => Ok(Arg::Pair(ArgsPair {
                 long: "--exclude-extension",
                 short: "-xe",
             })),
-            "-xp" => Ok(Arg::Pair(ArgsPair {
+            "-xp" | "--exclude-path" => Ok(Arg::Pair(ArgsPair {
                 long: "--exclude-path",
                 short: "-xp",
             })),
+            "-d" | "--dictionary" => Ok(Arg::Pair(ArgsPair {
+                long: "--dictionary",
+                short: "-d",
+            })),
+            "-b" | "--builtin" => Ok(Arg::Pair(ArgsPair {
+                long: "--builtin",
+                short: "-b",
+            })),
+            "-ir" | "--ignore-regex" => Ok(Arg::Pair(ArgsPair {
+                long: "--ignore-regex",
+                short: "-ir",
+            })),
+            "-rw" | "--repeated-words" => Ok(Arg::Pair(ArgsPair {
+                long: "--repeated-words",
+                short: "-rw",
+            })),
+            "-dr" | "--dictionary-rule" => Ok(Arg::Pair(ArgsPair {
+                long: "--dictionary-rule",
+                short: "-dr",
+            })),
+            "-enc" | "--encoding" => Ok(Arg::Pair(ArgsPair {
+                long: "--encoding",
+                short: "-enc",
+            })),
+            "-er" | "--encoding-rule" => Ok(Arg::Pair(ArgsPair {
+                long: "--encoding-rule",
+                short: "-er",
+            })),
             _ => {
                 Err("you tried to get invalid arg name, misconfiguration in your code".to_string())
             }
@@ -61,28 +166,54 @@ fn is_arg_valid(str: &str) -> bool {
     Arg::get(str).is_ok()
 }
 
-fn handle_file_extension(
+// flags don't take a value
+fn is_flag(str: &str) -> bool {
+    matches!(
+        str,
+        "--fix"
+            | "--staged"
+            | "--force"
+            | "--watch"
+            | "--no-cache"
+            | "--stats"
+            | "--unique"
+            | "-rw"
+            | "--repeated-words"
+    )
+}
+
+fn handle_flag_args(arg: &Arg, options_hashmap: &HashMap<String, Vec<String>>, target: &mut bool) {
+    match arg {
+        Arg::Single(arg) => *target |= options_hashmap.contains_key(*arg),
+        Arg::Pair(arg) => {
+            *target |= options_hashmap.contains_key(arg.short);
+            *target |= options_hashmap.contains_key(arg.long);
+        }
+    }
+}
+
+fn handle_string_arg(
     arg: &str,
     options_hashmap: &HashMap<String, Vec<String>>,
-    target_string: &mut Vec<String>,
+    values: &mut Vec<String>,
 ) {
     if let Some(entries) = options_hashmap.get(arg) {
-        for ext in entries {
-            target_string.push(ext.to_string());
+        for entry in entries {
+            values.push(entry.to_string());
         }
     }
 }
 
-fn handle_file_extensions(
+fn handle_string_args(
     arg: &Arg,
     options_hashmap: &HashMap<String, Vec<String>>,
-    target_string: &mut Vec<String>,
+    values: &mut Vec<String>,
 ) {
     match arg {
-        Arg::Single(arg) => handle_file_extension(arg, options_hashmap, target_string),
+        Arg::Single(arg) => handle_string_arg(arg, options_hashmap, values),
         Arg::Pair(arg) => {
-            handle_file_extension(arg.short, options_hashmap, target_string);
-            handle_file_extension(arg.long, options_hashmap, target_string);
+            handle_string_arg(arg.short, options_hashmap, values);
+            handle_string_arg(arg.long, options_hashmap, values);
         }
     }
 }
@@ -91,12 +222,14 @@ fn handle_path_buf_arg(
     arg: &str,
     options_hashmap: &HashMap<String, Vec<String>>,
     path_bufs: &mut Vec<PathBuf>,
+    allow_stdin: bool,
 ) -> Result<(), String> {
     if let Some(entries) = options_hashmap.get(arg) {
         for entry in entries.iter() {
             let mut path_buf = PathBuf::new();
             path_buf.push(entry);
-            if !path_buf.exists() {
+            let stdin = allow_stdin && entry == STDIN_TARGET;
+            if !stdin && !path_buf.exists() {
                 return Err(format!("path {} doesnt exist", entry));
             }
 
@@ -110,12 +243,199 @@ fn handle_path_buf_args(
     arg: &Arg,
     options_hashmap: &HashMap<String, Vec<String>>,
     path_bufs: &mut Vec<PathBuf>,
+    allow_stdin: bool,
 ) -> Result<(), String> {
     match arg {
-        Arg::Single(arg) => handle_path_buf_arg(arg, options_hashmap, path_bufs)?,
+        Arg::Single(arg) => handle_path_buf_arg(arg, options_hashmap, path_bufs, allow_stdin)?,
         Arg::Pair(arg) => {
-            handle_path_buf_arg(arg.short, options_hashmap, path_bufs)?;
-            handle_path_buf_arg(arg.long, options_hashmap, path_bufs)?;
+            handle_path_buf_arg(arg.short, options_hashmap, path_bufs, allow_stdin)?;
+            handle_path_buf_arg(arg.long, options_hashmap, path_bufs, allow_stdin)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_wordlist_arg(
+    arg: &str,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    wordlists: &mut Vec<NamedWordlist>,
+    named: bool,
+) -> Result<(), String> {
+    if let Some(entries) = options_hashmap.get(arg) {
+        for entry in entries.iter() {
+            let (name, path) = if named {
+                match entry.split_once('=') {
+                    Some((name, path)) if !name.is_empty() => (name, path),
+                    _ => return Err(format!("dictionary {entry} should be in name=path format")),
+                }
+            } else {
+                (DEFAULT_DICTIONARY, entry.as_str())
+            };
+
+            let path_buf = PathBuf::from(path);
+            if !path_buf.exists() {
+                return Err(format!("path {} doesnt exist", path));
+            }
+
+            wordlists.push(NamedWordlist {
+                name: name.to_string(),
+                path: path_buf,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn handle_wordlist_args(
+    arg: &Arg,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    wordlists: &mut Vec<NamedWordlist>,
+    named: bool,
+) -> Result<(), String> {
+    match arg {
+        Arg::Single(arg) => handle_wordlist_arg(arg, options_hashmap, wordlists, named)?,
+        Arg::Pair(arg) => {
+            handle_wordlist_arg(arg.short, options_hashmap, wordlists, named)?;
+            handle_wordlist_arg(arg.long, options_hashmap, wordlists, named)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_dictionary_rule_arg(
+    arg: &str,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    rules: &mut Vec<DictionaryRule>,
+) -> Result<(), String> {
+    if let Some(entries) = options_hashmap.get(arg) {
+        for entry in entries.iter() {
+            let Some((pattern, names)) = entry.rsplit_once('=') else {
+                return Err(format!(
+                    "dictionary rule {entry} should be in glob=name1,name2 format"
+                ));
+            };
+
+            let dictionaries: Vec<String> = names
+                .split(',')
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect();
+
+            if pattern.is_empty() || dictionaries.is_empty() {
+                return Err(format!(
+                    "dictionary rule {entry} should be in glob=name1,name2 format"
+                ));
+            }
+
+            rules.push(DictionaryRule {
+                pattern: pattern.to_string(),
+                dictionaries,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn handle_dictionary_rule_args(
+    arg: &Arg,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    rules: &mut Vec<DictionaryRule>,
+) -> Result<(), String> {
+    match arg {
+        Arg::Single(arg) => handle_dictionary_rule_arg(arg, options_hashmap, rules)?,
+        Arg::Pair(arg) => {
+            handle_dictionary_rule_arg(arg.short, options_hashmap, rules)?;
+            handle_dictionary_rule_arg(arg.long, options_hashmap, rules)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_encoding(name: &str) -> Result<Encoding, String> {
+    Encoding::from_name(name.trim()).ok_or_else(|| {
+        format!("unknown encoding {name}, use utf-8, utf-16le, utf-16be, windows-1252 or latin1")
+    })
+}
+
+fn handle_encoding_arg(
+    arg: &str,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    encoding: &mut Encoding,
+    rules: &mut Vec<EncodingRule>,
+) -> Result<(), String> {
+    if let Some(entries) = options_hashmap.get(arg) {
+        if arg == "-enc" || arg == "--encoding" {
+            *encoding = parse_encoding(&entries[0])?;
+            return Ok(());
+        }
+
+        for entry in entries.iter() {
+            let Some((pattern, name)) = entry.rsplit_once('=') else {
+                return Err(format!(
+                    "encoding rule {entry} should be in glob=encoding format"
+                ));
+            };
+            if pattern.is_empty() {
+                return Err(format!(
+                    "encoding rule {entry} should be in glob=encoding format"
+                ));
+            }
+
+            rules.push(EncodingRule {
+                pattern: pattern.to_string(),
+                encoding: parse_encoding(name)?,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn handle_encoding_args(
+    arg: &Arg,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    encoding: &mut Encoding,
+    rules: &mut Vec<EncodingRule>,
+) -> Result<(), String> {
+    match arg {
+        Arg::Single(arg) => handle_encoding_arg(arg, options_hashmap, encoding, rules)?,
+        Arg::Pair(arg) => {
+            handle_encoding_arg(arg.short, options_hashmap, encoding, rules)?;
+            handle_encoding_arg(arg.long, options_hashmap, encoding, rules)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_regex_arg(
+    arg: &str,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    regexes: &mut Vec<Regex>,
+) -> Result<(), String> {
+    if let Some(entries) = options_hashmap.get(arg) {
+        for entry in entries.iter() {
+            match Regex::new(entry) {
+                Ok(regex) => regexes.push(regex),
+                Err(error) => return Err(format!("invalid regex {entry}: {error}")),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn handle_regex_args(
+    arg: &Arg,
+    options_hashmap: &HashMap<String, Vec<String>>,
+    regexes: &mut Vec<Regex>,
+) -> Result<(), String> {
+    match arg {
+        Arg::Single(arg) => handle_regex_arg(arg, options_hashmap, regexes)?,
+        Arg::Pair(arg) => {
+            handle_regex_arg(arg.short, options_hashmap, regexes)?;
+            handle_regex_arg(arg.long, options_hashmap, regexes)?;
         }
     }
 
@@ -163,66 +483,356 @@ fn push_args_into_struct(
     options: &mut Options,
     options_hashmap: &mut HashMap<String, Vec<String>>,
 ) -> Result<(), String> {
-    handle_path_buf_args(&Arg::get("-t")?, options_hashmap, &mut options.targets)?;
-    handle_path_buf_args(&Arg::get("-w")?, options_hashmap, &mut options.wordlists)?;
-    handle_path_buf_args(&Arg::get("-i")?, options_hashmap, &mut options.ignore)?;
+    handle_path_buf_args(
+        &Arg::get("-t")?,
+        options_hashmap,
+        &mut options.targets,
+        true,
+    )?;
+    if let Some(entries) = options_hashmap.get("--stdin-filename") {
+        options.stdin_filename = Some(PathBuf::from(&entries[0]));
+    }
+    if let Some(entries) = options_hashmap.get("--diff-base") {
+        options.diff_base = Some(entries[0].to_string());
+    }
+    if let Some(entries) = options_hashmap.get("--format") {
+        match Format::from_name(&entries[0]) {
+            Some(format) => options.format = format,
+            None => return Err(format!("unknown format {}, use text or json", entries[0])),
+        }
+    }
+    if let Some(entries) = options_hashmap.get("--group-by") {
+        match entries[0].as_str() {
+            GROUP_BY_WORD => options.group_by_word = true,
+            _ => return Err(format!("unknown grouping {}, use word", entries[0])),
+        }
+    }
+    if let Some(entries) = options_hashmap.get("--variant") {
+        match VariantMode::from_name(&entries[0]) {
+            Some(variant) => options.variant = Some(variant),
+            None => {
+                return Err(format!(
+                    "unknown variant {}, use us, uk or auto",
+                    entries[0]
+                ))
+            }
+        }
+    }
+    handle_wordlist_args(
+        &Arg::get("-w")?,
+        options_hashmap,
+        &mut options.wordlists,
+        false,
+    )?;
+    handle_wordlist_args(
+        &Arg::get("-d")?,
+        options_hashmap,
+        &mut options.wordlists,
+        true,
+    )?;
+    handle_dictionary_rule_args(
+        &Arg::get("-dr")?,
+        options_hashmap,
+        &mut options.dictionary_rules,
+    )?;
+    handle_path_buf_args(
+        &Arg::get("-i")?,
+        options_hashmap,
+        &mut options.ignore,
+        false,
+    )?;
+    handle_path_buf_args(
+        &Arg::get("-sr")?,
+        options_hashmap,
+        &mut options.style_rules,
+        false,
+    )?;
     handle_path_buf_args(
         &Arg::get("-xp")?,
         options_hashmap,
         &mut options.paths_to_exclude,
+        false,
     )?;
 
-    handle_file_extensions(&Arg::get("-e")?, options_hashmap, &mut options.extensions);
-    handle_file_extensions(
+    handle_string_args(&Arg::get("-e")?, options_hashmap, &mut options.extensions);
+    handle_string_args(
         &Arg::get("-xe")?,
         options_hashmap,
         &mut options.extensions_to_exclude,
     );
 
+    handle_string_args(
+        &Arg::get("-b")?,
+        options_hashmap,
+        &mut options.builtin_dictionaries,
+    );
+
+    handle_regex_args(
+        &Arg::get("-ir")?,
+        options_hashmap,
+        &mut options.ignore_regexes,
+    )?;
+
+    handle_encoding_args(
+        &Arg::get("-enc")?,
+        options_hashmap,
+        &mut options.encoding,
+        &mut options.encoding_rules,
+    )?;
+    handle_encoding_args(
+        &Arg::get("-er")?,
+        options_hashmap,
+        &mut options.encoding,
+        &mut options.encoding_rules,
+    )?;
+
+    handle_flag_args(
+        &Arg::get("-rw")?,
+        options_hashmap,
+        &mut options.repeated_words,
+    );
+    handle_flag_args(&Arg::get("--fix")?, options_hashmap, &mut options.fix);
+    handle_flag_args(&Arg::get("--staged")?, options_hashmap, &mut options.staged);
+    handle_flag_args(&Arg::get("--force")?, options_hashmap, &mut options.force);
+    handle_flag_args(&Arg::get("--watch")?, options_hashmap, &mut options.watch);
+    handle_flag_args(&Arg::get("--stats")?, options_hashmap, &mut options.stats);
+    handle_flag_args(&Arg::get("--unique")?, options_hashmap, &mut options.unique);
+    let mut no_cache = false;
+    handle_flag_args(&Arg::get("--no-cache")?, options_hashmap, &mut no_cache);
+    options.cache = !no_cache;
+
     handle_int_args(&Arg::get("--min")?, options_hashmap, &mut options.min)?;
     handle_int_args(&Arg::get("--max")?, options_hashmap, &mut options.max)?;
+    handle_int_args(&Arg::get("--top")?, options_hashmap, &mut options.top)?;
+    handle_int_args(
+        &Arg::get("--context")?,
+        options_hashmap,
+        &mut options.context,
+    )?;
 
     Ok(())
 }
 
-pub fn get_options_with_argv(argv: Args) -> Result<Options, String> {
-    let argv: Vec<String> = argv.collect();
+pub fn get_options_with_argv(argv: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let argv: Vec<String> = argv.into_iter().collect();
     let argv_len = argv.len();
-    if argv_len <= 1 || argv_len % 2 == 0 {
+    if argv_len <= 1 {
         return Err("number of arguments is invalid".to_string());
     }
     let mut options = Options::new();
     let mut options_hashmap: HashMap<String, Vec<String>> = HashMap::new();
 
-    for i in (1..argv_len).step_by(2) {
+    let mut i = 1;
+    // rscheck commit-msg <file> [args]
+    if argv[1] == COMMIT_MSG_COMMAND {
+        let Some(file) = argv.get(2) else {
+            return Err("commit-msg needs a message file".to_string());
+        };
+        options_hashmap.insert("-t".to_string(), vec![file.to_string()]);
+        options.commit_msg = true;
+        i = 3;
+    }
+    // rscheck lsp [args], documents come from the editor
+    if argv[1] == LSP_COMMAND {
+        options.lsp = true;
+        i = 2;
+    }
+    // rscheck dict <action> [words or wordlists] [args]
+    if argv[1] == DICT_COMMAND {
+        let Some(action) = argv.get(2) else {
+            return Err("dict needs an action".to_string());
+        };
+        if ![
+            dict::ADD,
+            dict::REMOVE,
+            dict::SORT,
+            dict::DEDUPE,
+            dict::LINT,
+        ]
+        .contains(&action.as_str())
+        {
+            return Err(format!("unknown dict action {action}"));
+        }
+        options.dict = Some(DictCommand {
+            action: action.to_string(),
+            args: Vec::new(),
+            file: None,
+        });
+        i = 3;
+    }
+    // rscheck learn [targets] [args]
+    if argv[1] == LEARN_COMMAND {
+        options.learn = Some(LearnCommand {
+            output: None,
+            min_count: 1,
+        });
+        i = 2;
+    }
+    // rscheck hook install [kind] [--force] [args], the args are passed on
+    // to rscheck in the hook
+    if argv[1] == HOOK_COMMAND {
+        if argv.get(2).map(String::as_str) != Some("install") {
+            return Err("hook only supports install".to_string());
+        }
+        let mut args = &argv[3..];
+        let kind = match args.first() {
+            Some(kind) if !kind.starts_with('-') => {
+                args = &args[1..];
+                kind.to_string()
+            }
+            _ => hook::PRE_COMMIT.to_string(),
+        };
+        options.force = args.iter().any(|arg| arg == "--force");
+        let args: Vec<String> = args
+            .iter()
+            .filter(|arg| *arg != "--force")
+            .cloned()
+            .collect();
+
+        // a hook with invalid args would block every commit
+        if let Some(hook_argv) = hook::hook_argv(&kind, &args) {
+            get_options_with_argv(hook_argv)
+                .map_err(|error| format!("invalid args for the {kind} hook: {error}"))?;
+        }
+        options.hook = Some(HookCommand { kind, args });
+        return Ok(options);
+    }
+    while i < argv_len {
         let arg = &argv[i];
 
+        // anything that isn't an arg is a target, e.g. files passed by
+        // pre-commit
         if !is_arg_valid(arg) {
-            return Err("invalid argument provided".to_string());
+            if arg.starts_with('-') && arg != STDIN_TARGET {
+                return Err("invalid argument provided".to_string());
+            }
+            if let Some(dict) = options.dict.as_mut() {
+                dict.args.push(arg.to_string());
+                i += 1;
+                continue;
+            }
+            options_hashmap
+                .entry("-t".to_string())
+                .or_default()
+                .push(arg.to_string());
+            i += 1;
+            continue;
+        }
+
+        if is_flag(arg) {
+            options_hashmap
+                .entry(arg.to_string())
+                .or_default()
+                .push(String::new());
+            i += 1;
+            continue;
         }
 
+        let Some(value) = argv.get(i + 1) else {
+            return Err("number of arguments is invalid".to_string());
+        };
+
         options_hashmap
             .entry(arg.to_string())
             .or_default()
-            .push(argv[i + 1].to_string());
+            .push(value.to_string());
+        i += 2;
     }
 
     push_args_into_struct(&mut options, &mut options_hashmap)?;
 
-    if options.targets.is_empty() {
+    if let Some(learn) = options.learn.as_mut() {
+        learn.output = options_hashmap
+            .get("--to")
+            .map(|entries| PathBuf::from(&entries[0]));
+        handle_int_args(
+            &Arg::get("--min-count")?,
+            &options_hashmap,
+            &mut learn.min_count,
+        )?;
+    }
+
+    if let Some(dict) = options.dict.as_mut() {
+        dict.file = options_hashmap
+            .get("--to")
+            .or_else(|| options_hashmap.get("--from"))
+            .map(|entries| PathBuf::from(&entries[0]));
+
+        let needs_file = dict.action == dict::ADD || dict.action == dict::REMOVE;
+        if needs_file && dict.file.is_none() {
+            return Err(format!(
+                "dict {} needs a wordlist as --to or --from",
+                dict.action
+            ));
+        }
+        if dict.args.is_empty() {
+            return Err(format!("dict {} needs words or wordlists", dict.action));
+        }
+        if !needs_file {
+            if let Some(path) = dict.args.iter().find(|path| !Path::new(path).exists()) {
+                return Err(format!("path {path} doesnt exist"));
+            }
+        }
+        return Ok(options);
+    }
+
+    if options.targets.is_empty() && !options.lsp {
         return Err("no target was provided".to_string());
     }
 
-    if options.wordlists.is_empty() {
+    if options.builtin_dictionaries.is_empty() && options.wordlists.is_empty() {
+        options.builtin_dictionaries = default_builtins();
+    }
+
+    for name in options.builtin_dictionaries.iter() {
+        if !is_builtin(name) && name != builtin::NONE {
+            return Err(format!("there is no builtin dictionary named {name}"));
+        }
+    }
+
+    if options
+        .builtin_dictionaries
+        .iter()
+        .any(|name| name == builtin::NONE)
+    {
+        options.builtin_dictionaries.clear();
+    }
+
+    if options.builtin_dictionaries.is_empty() && options.wordlists.is_empty() {
         return Err("no wordlists was provided".to_string());
     }
 
+    for rule in options.dictionary_rules.iter() {
+        for name in rule.dictionaries.iter() {
+            if !options
+                .wordlists
+                .iter()
+                .any(|wordlist| &wordlist.name == name)
+                && !options.builtin_dictionaries.contains(name)
+            {
+                return Err(format!(
+                    "dictionary {name} used in rule {} was not provided",
+                    rule.pattern
+                ));
+            }
+        }
+    }
+
     Ok(options)
 }
 
+fn default_builtins() -> Vec<String> {
+    vec![builtin::ENGLISH.to_string(), builtin::CODE.to_string()]
+}
+
+// like running without args, so a Checker built from the default options
+// checks English text and code with the builtin dictionaries
 impl Default for Options {
     fn default() -> Self {
-        Self::new()
+        Self {
+            builtin_dictionaries: default_builtins(),
+            ..Self::new()
+        }
     }
 }
 
@@ -230,16 +840,43 @@ impl Options {
     fn new() -> Self {
         Self {
             targets: Vec::new(),
+            stdin_filename: None,
             wordlists: Vec::new(),
+            builtin_dictionaries: Vec::new(),
+            dictionary_rules: Vec::new(),
             ignore: Vec::new(),
+            style_rules: Vec::new(),
             paths_to_exclude: Vec::new(),
             extensions: Vec::new(),
             extensions_to_exclude: Vec::new(),
+            ignore_regexes: Vec::new(),
+            variant: None,
+            format: Format::Text,
+            stats: false,
+            top: 10,
+            group_by_word: false,
+            unique: false,
+            context: 0,
+            encoding: Encoding::Utf8,
+            encoding_rules: Vec::new(),
+            repeated_words: false,
+            fix: false,
+            diff_base: None,
+            staged: false,
+            commit_msg: false,
+            hook: None,
+            force: false,
+            lsp: false,
+            watch: false,
+            cache: true,
+            dict: None,
+            learn: None,
             min: 2,
             max: 20,
         }
     }
-    pub fn create(argv: Args) -> Result<Options, String> {
+    // argv starts with the program name like env::args()
+    pub fn create(argv: impl IntoIterator<Item = String>) -> Result<Options, String> {
         get_options_with_argv(argv)
     }
 }
@@ -247,17 +884,122 @@ impl Options {
 pub fn show_manual() {
     let msg = r###"===USAGE===
 rscheck -t [target] -w [wordlist]
--t or --target - set a target file or directory
+rscheck commit-msg [message file] -w [wordlist]
+rscheck hook install [pre-commit or commit-msg] [--force] [args for the hook]
+rscheck lsp -w [wordlist]
+rscheck learn -t [target] [--to candidates.txt] [--min-count 2]
+rscheck dict add [words] --to [wordlist], rscheck dict remove [words] --from [wordlist]
+rscheck dict sort [wordlists], rscheck dict dedupe [wordlists], rscheck dict lint [wordlists] -i [ignore list]
+-t or --target - set a target file or directory, - reads text from stdin, values without an arg are targets too
 -w or --wordlist - set a wordlist with valid words (optional)
+-b or --builtin - use a builtin dictionary, "en" (English) or "code" (programming terms), "none" disables them (optional)
 -i or --ignore - set wordlist of words to be ignored (optional)
+-sr or --style-rules - set a file of style rules like whitelist -> allowlist, or a single forbidden term per line, checked even for correct words (optional)
 -e or --extension - set extension to scan (optional)
 -xe or --exclude-extension - exclude specific extension (optional)
 -xp or --exclude-path - exclude specific directory or file (optional)
+-ir or --ignore-regex - skip text matching a regex in lines and tokens (optional)
+-rw or --repeated-words - report repeated words like "the the", also across lines of the same paragraph (optional)
+--fix - fix typos that can be fixed automatically, like repeated words (optional)
+--diff-base - only report typos on lines changed since a git revision (optional)
+--staged - only report typos on staged lines, compared to HEAD or to --diff-base (optional)
+--watch - check targets again when files change, changed wordlists and ignore lists are reloaded (optional)
+--no-cache - don't read or write results of unchanged files in .rscheck-cache/ (optional)
+--stdin-filename - path used to select dictionary and encoding rules for text read from stdin (optional)
+--variant - report words spelled in the other English variant, us, uk or auto to follow the variant most words of the targets use (optional)
+--format - text (default) or json, json prints typos and the summary as one document and notes to stderr (optional)
+--stats - extend the summary with words and lines checked, skipped files by rule, elapsed time, problems by file, extension and directory and the most frequent typos (optional)
+--top - number of entries of each --stats list, 10 by default (optional)
+--group-by word - list each problem word once with its count, suggestions and all locations (optional)
+--unique - print only the distinct misspelled words, written like wordlist entries, e.g. to append them to a wordlist (optional)
+--context - number of source lines shown before and after each typo, 0 by default (optional)
 --min and --max - minimum and maximum length of word (optional)
+-d or --dictionary - set a named wordlist as name=path, wordlists with the same name are merged (optional)
+-dr or --dictionary-rule - check files matching a glob only with given dictionaries as glob=name1,name2 (optional)
+-enc or --encoding - encoding of files without a byte order mark, utf-8 (default), utf-16le, utf-16be, windows-1252 or latin1 (optional)
+-er or --encoding-rule - set encoding of files matching a glob as glob=encoding (optional)
+
+Builtin "en" and "code" dictionaries are used when no wordlist is provided, pass -b to use them together with wordlists
+Wordlist entries with capitals like GitHub must be written exactly like that or in all caps, lowercase entries match any casing
+Wordlists have one entry per line, lines are trimmed and blank lines and # comments are skipped
+Entries can end with flags like npm [case] or colour [forbidden, nosuggest]: case and nocase set case sensitivity, forbidden words are always reported, nosuggest words are never suggested
+Typos are shown with their source line and the word underlined, colors are used when stdout is a terminal and NO_COLOR isn't set
+Tokens joined with -, . or / like my-crate-name or src/main.rs are checked word by word and typos are reported with the whole token
+Urls, email addresses, uuids, base64 data, hex literals and hashes are skipped
+Contractions like don't are looked up whole and the possessive 's is ignored
+Text read from stdin is never fixed, --fix only writes files
+Style rules take an optional id like e-mail -> email [terms], preferred terms are fixed with --fix
+Variant spellings like colour with --variant us are fixed with --fix, auto doesn't report anything when both variants are used equally
+Typos, casing errors, repeated words, style rule violations and variant spellings make rscheck exit with 1
+hook install writes a git hook checking the lines of staged files or the commit message, other args like -w are passed on to rscheck in the hook, --force replaces a hook not written by rscheck
+learn lists words missing from the dictionaries by how often and in how many files they are used, --to writes them for review
+dict add keeps sorted wordlists sorted, dict lint reports duplicates, ignored words, words outside --min and --max, invalid characters and whitespace
+lsp serves diagnostics and code actions over stdio, words added from the editor go to .rscheck-words.txt in the project root, which every check in that directory loads into the default dictionary
+commit-msg skips comments, the diff of git commit -v, trailers and issue references, suggests fixes and exits with 1 on typos
+Results are cached by file content, wordlists and options, results of other configurations and unused for a week are pruned
+Changed lines are read from the local git repository, with --staged the staged content is checked and nothing is fixed
+Files starting with a UTF-8 or UTF-16 byte order mark are always decoded with it, --fix writes files back in the same encoding
 
 Args can be combined like
 rscheck -t file.txt -t dir/ -w wordlist.txt -w wordlist2.txt -i to_be_ignored.txt -e rs -e cpp -xp dir/subdir/
 
---min and --max are the only args that can be used only once"###;
+Wordlists passed with -w belong to the "default" dictionary. Files not matched by any rule are checked against all dictionaries
+rscheck -t docs/ -d en_US=en.txt -d de_DE=de.txt -d code=code.txt -dr docs/de/**=de_DE -dr docs/en/**=en_US -dr *.rs=en_US,code
+rscheck -t res/ -enc windows-1252 -er *.rc=utf-16le
+git show HEAD:README.md | rscheck -t - --stdin-filename README.md
+rscheck -t . --diff-base origin/main
+
+--min, --max, --encoding, --stdin-filename, --diff-base, --variant, --format, --top, --group-by, --context, --to, --from and --min-count are the only args that can be used only once, -rw, --fix, --staged, --force, --watch, --no-cache, --stats and --unique don't take a value"###;
     println!("{msg}");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::spell_checker::options::Options;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::create(["rscheck"].iter().chain(args).map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn check_positional_targets() {
+        let options = parse(&["src", "-t", "README.md", "--staged", "Cargo.toml"]).unwrap();
+        assert_eq!(
+            options.targets,
+            ["src", "README.md", "Cargo.toml"].map(PathBuf::from)
+        );
+        assert!(options.staged);
+        assert!(parse(&["src", "--unknown"]).is_err());
+
+        let options = parse(&["hook", "install", "--force", "-w", "Cargo.toml"]).unwrap();
+        let hook = options.hook.unwrap();
+        assert_eq!(hook.kind, "pre-commit");
+        assert_eq!(hook.args, ["-w", "Cargo.toml"]);
+        assert!(options.force);
+        assert!(options.targets.is_empty());
+        assert!(parse(&["hook", "install", "commit-msg", "--min"]).is_err());
+    }
+
+    #[test]
+    fn check_builtin_dictionaries() {
+        let options = parse(&["-"]).unwrap();
+        assert_eq!(options.builtin_dictionaries, ["en", "code"]);
+        assert_eq!(
+            parse(&["-", "-b", "code"]).unwrap().builtin_dictionaries,
+            ["code"]
+        );
+        // a wordlist replaces the default builtins
+        let options = parse(&["-", "-w", "Cargo.toml"]).unwrap();
+        assert!(options.builtin_dictionaries.is_empty());
+
+        let options = parse(&["-", "-b", "none", "-w", "Cargo.toml"]).unwrap();
+        assert!(options.builtin_dictionaries.is_empty());
+        assert_eq!(options.wordlists.len(), 1);
+        assert!(parse(&["-", "-b", "none"]).is_err());
+
+        let error = parse(&["-", "-b", "fr"]).err().unwrap();
+        assert!(error.contains("no builtin dictionary named fr"));
+    }
+}
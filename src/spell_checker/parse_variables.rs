@@ -1,17 +1,11 @@
 pub fn is_camel_case(str: &str) -> bool {
     let mut lower_case_found = false;
     let mut upper_case_found = false;
-    let mut was_previous_upper = false;
 
     for ch in str.chars() {
         if ch.is_lowercase() {
-            was_previous_upper = false;
             lower_case_found = true;
         } else if ch.is_uppercase() {
-            if was_previous_upper {
-                return false;
-            }
-            was_previous_upper = true;
             upper_case_found = true;
         } else if !ch.is_ascii_digit() {
             return false;
@@ -28,39 +22,101 @@ pub fn is_snake_case(str: &str) -> bool {
         && !str.ends_with('_')
 }
 
-pub fn parse_camel_case(str: &str) -> Option<Vec<String>> {
-    if !is_camel_case(str) {
-        return None;
+pub fn is_alphanumeric(str: &str) -> bool {
+    str.chars().any(|ch| ch.is_ascii_digit())
+        && str.chars().any(|ch| ch.is_alphabetic())
+        && str.chars().all(|ch| ch.is_alphanumeric())
+}
+
+fn push_word(result: &mut Vec<String>, buffer: &mut String) {
+    if buffer.chars().any(|ch| ch.is_alphabetic()) {
+        result.push(buffer.clone());
     }
+    buffer.clear();
+}
+
+// underscores and digits separate words, a run of capitals is an acronym
+// which ends before the capital starting the next word, e.g. HTTP + Server
+pub fn split_words(str: &str) -> Vec<String> {
+    let chars: Vec<char> = str.chars().collect();
     let mut result: Vec<String> = Vec::new();
     let mut buffer = String::new();
+    let mut i = 0;
 
-    for ch in str.chars() {
-        if ch.is_uppercase() && !buffer.is_empty() {
-            result.push(buffer.to_lowercase());
-            buffer.clear();
+    while i < chars.len() {
+        let ch = chars[i];
+        let previous = if i > 0 { Some(chars[i - 1]) } else { None };
+        let next = chars.get(i + 1);
+
+        if ch == '_' || ch.is_ascii_digit() {
+            push_word(&mut result, &mut buffer);
+        } else if ch.is_uppercase() {
+            match previous {
+                Some(previous) if previous.is_lowercase() => push_word(&mut result, &mut buffer),
+                Some(previous)
+                    if previous.is_uppercase() && next.is_some_and(|ch| ch.is_lowercase()) =>
+                {
+                    // plural acronyms like URLs or IDs
+                    if chars[i + 1] == 's' && chars.get(i + 2).is_none_or(|ch| !ch.is_lowercase()) {
+                        buffer.push(ch);
+                        push_word(&mut result, &mut buffer);
+                        i += 2;
+                        continue;
+                    }
+                    push_word(&mut result, &mut buffer);
+                }
+                _ => {}
+            }
+            buffer.push(ch);
+        } else {
+            buffer.push(ch);
         }
-        buffer.push(ch);
+        i += 1;
     }
 
-    if !buffer.is_empty() {
-        result.push(buffer.to_lowercase());
+    push_word(&mut result, &mut buffer);
+
+    result
+}
+
+fn split_if_compound(str: &str) -> Option<Vec<String>> {
+    let result = split_words(str);
+
+    if result.len() == 1 && result[0] == str {
+        return None;
     }
 
     Some(result)
 }
 
+pub fn parse_camel_case(str: &str) -> Option<Vec<String>> {
+    if !is_camel_case(str) {
+        return None;
+    }
+    split_if_compound(str)
+}
+
 pub fn parse_snake_case(str: &str) -> Option<Vec<String>> {
     if !is_snake_case(str) {
         return None;
     }
-    Some(str.trim().split('_').map(|x| x.to_lowercase()).collect())
+    split_if_compound(str.trim())
+}
+
+pub fn parse_alphanumeric(str: &str) -> Option<Vec<String>> {
+    if !is_alphanumeric(str) {
+        return None;
+    }
+    split_if_compound(str)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::spell_checker::parse_variables::is_camel_case;
     use crate::spell_checker::parse_variables::is_snake_case;
+    use crate::spell_checker::parse_variables::{
+        parse_alphanumeric, parse_camel_case, parse_snake_case,
+    };
 
     #[test]
     fn check_snake_case_checker() {
@@ -86,6 +142,64 @@ mod tests {
         assert!(is_camel_case("MyFunction"));
         assert!(is_camel_case("thisIsMyFunction"));
         assert!(is_camel_case("ThisIsMyFunction"));
-        assert!(!is_camel_case("THisIsMyFunction"));
+        assert!(is_camel_case("THisIsMyFunction"));
+        assert!(is_camel_case("HTTPServer"));
+        assert!(is_camel_case("utf8Decoder"));
+    }
+
+    #[test]
+    fn check_acronym_splitting() {
+        assert_eq!(
+            parse_camel_case("HTTPServer"),
+            Some(vec!["HTTP".to_string(), "Server".to_string()])
+        );
+        assert_eq!(
+            parse_camel_case("parseXMLDocument"),
+            Some(vec![
+                "parse".to_string(),
+                "XML".to_string(),
+                "Document".to_string()
+            ])
+        );
+        assert_eq!(
+            parse_camel_case("IOError"),
+            Some(vec!["IO".to_string(), "Error".to_string()])
+        );
+        assert_eq!(
+            parse_camel_case("getURLForID"),
+            Some(vec![
+                "get".to_string(),
+                "URL".to_string(),
+                "For".to_string(),
+                "ID".to_string()
+            ])
+        );
+        assert_eq!(
+            parse_camel_case("listURLs"),
+            Some(vec!["list".to_string(), "URL".to_string()])
+        );
+        assert_eq!(parse_camel_case("Github"), None);
+    }
+
+    #[test]
+    fn check_digit_and_mixed_splitting() {
+        assert_eq!(
+            parse_camel_case("utf8Decoder"),
+            Some(vec!["utf".to_string(), "Decoder".to_string()])
+        );
+        assert_eq!(
+            parse_camel_case("vec3Add"),
+            Some(vec!["vec".to_string(), "Add".to_string()])
+        );
+        assert_eq!(parse_alphanumeric("sha256"), Some(vec!["sha".to_string()]));
+        assert_eq!(
+            parse_snake_case("My_camelCase_name"),
+            Some(vec![
+                "My".to_string(),
+                "camel".to_string(),
+                "Case".to_string(),
+                "name".to_string()
+            ])
+        );
     }
 }
@@ -0,0 +1,114 @@
+use std::env;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+
+use super::options::Options;
+use super::typo::Typo;
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+// prints typos like a compiler, the source line with the word underlined
+// and optional lines around it
+pub struct Renderer {
+    color: bool,
+    context: usize,
+}
+
+// colors are only used on a terminal and never when NO_COLOR is set
+fn use_color() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && io::stdout().is_terminal()
+}
+
+impl Renderer {
+    pub fn new(options: &Options) -> Self {
+        Self {
+            color: use_color(),
+            context: usize::from(options.context),
+        }
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        match self.color {
+            true => format!("{color}{text}{RESET}"),
+            false => text.to_string(),
+        }
+    }
+
+    // lines are the decoded text of the file, tabs are shown as one space so
+    // the underline stays under the word
+    pub fn render(&self, typo: &Typo, lines: &[&str], suggestions: &[String]) -> String {
+        let mut output = format!("{typo}");
+        if !suggestions.is_empty() {
+            let suggestions = self.paint(GREEN, &suggestions.join(", "));
+            let _ = write!(output, " (did you mean {suggestions}?)");
+        }
+        output.push('\n');
+
+        if typo.line == 0 || typo.line > lines.len() {
+            return output;
+        }
+        let first = typo.line.saturating_sub(self.context).max(1);
+        let last = (typo.line + self.context).min(lines.len());
+        let width = last.to_string().len();
+        let gutter = |num: &str| self.paint(BLUE, &format!("    {num:>width$} |"));
+
+        let _ = writeln!(output, "{}", gutter(""));
+        for num in first..=last {
+            let line = lines[num - 1].replace('\t', " ");
+            let _ = writeln!(output, "{} {}", gutter(&num.to_string()), line.trim_end());
+
+            if num == typo.line {
+                let underline = "^".repeat(typo.word.chars().count().max(1));
+                let _ = writeln!(
+                    output,
+                    "{} {}{}",
+                    gutter(""),
+                    " ".repeat(typo.column.saturating_sub(1)),
+                    self.paint(RED, &underline)
+                );
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::render::Renderer;
+    use crate::spell_checker::typo::{Typo, TypoKind};
+
+    #[test]
+    fn check_rendering() {
+        let typo = Typo {
+            word: "Wrold".to_string(),
+            token: Some("getWrold".to_string()),
+            line: 2,
+            column: 9,
+            kind: TypoKind::Misspelled,
+            fix: None,
+        };
+        let lines = ["fn main() {", "\tlet getWrold = 1;", "}"];
+
+        let renderer = Renderer {
+            color: false,
+            context: 0,
+        };
+        assert_eq!(
+            renderer.render(&typo, &lines, &["World".to_string()]),
+            "  * Wrold:2 in getWrold (did you mean World?)\n      |\n    2 |  let getWrold = 1;\n      |         ^^^^^\n"
+        );
+
+        let renderer = Renderer {
+            color: false,
+            context: 1,
+        };
+        let rendered = renderer.render(&typo, &lines, &[]);
+        assert_eq!(rendered.lines().count(), 6);
+        assert!(rendered.contains("    1 | fn main() {"));
+    }
+}
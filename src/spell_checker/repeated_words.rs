@@ -0,0 +1,167 @@
+use super::tokenizer::Token;
+use super::typo::{Fix, Position, Typo, TypoKind};
+
+struct LastWord {
+    text: String,
+    start: Position,
+    end: Position,
+}
+
+// finds words written twice in a row, a word at the end of a line and one at
+// the start of the next line count as neighbours unless the paragraph ends
+pub struct RepeatedWords {
+    last: Option<LastWord>,
+}
+
+// text allowed between repeated words on different lines, so comments
+// spanning several lines are checked as a whole. List and quote markers like
+// - and > start a new item, so equal items on consecutive lines aren't repeats
+fn is_line_continuation(text: &str) -> bool {
+    text.chars()
+        .all(|ch| ch.is_whitespace() || matches!(ch, '/' | '*' | '#' | ';' | '!'))
+}
+
+fn slice_chars(chars: &[char], start: usize, end: usize) -> String {
+    chars[start.min(chars.len())..end.min(chars.len())]
+        .iter()
+        .collect()
+}
+
+impl Default for RepeatedWords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RepeatedWords {
+    pub fn new() -> Self {
+        Self { last: None }
+    }
+
+    pub fn check_line(&mut self, line: &str, tokens: &[Token], line_num: usize) -> Vec<Typo> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut typos: Vec<Typo> = Vec::new();
+        let mut previous_end = 0;
+
+        for (i, token) in tokens.iter().enumerate() {
+            let gap = slice_chars(&chars, previous_end, token.column);
+            let adjacent = if i == 0 {
+                is_line_continuation(&gap)
+            } else {
+                gap.chars().all(char::is_whitespace)
+            };
+
+            let start = Position {
+                line: line_num,
+                column: token.column + 1,
+            };
+            let end_column = token.column + token.text.chars().count();
+            let end = Position {
+                line: line_num,
+                column: end_column + 1,
+            };
+
+            if let Some(last) = self.last.as_ref().filter(|_| adjacent) {
+                if last.text == token.text.to_lowercase() {
+                    typos.push(Typo {
+                        word: token.text.clone(),
+                        token: None,
+                        line: line_num,
+                        column: start.column,
+                        kind: TypoKind::Repeated(last.start),
+                        fix: Some(self.removal(last, start, end, &chars, end_column)),
+                    });
+                }
+            }
+
+            self.last = Some(LastWord {
+                text: token.text.to_lowercase(),
+                start,
+                end,
+            });
+            previous_end = end_column;
+        }
+
+        if tokens.is_empty()
+            || !is_line_continuation(&slice_chars(&chars, previous_end, chars.len()))
+        {
+            self.last = None;
+        }
+
+        typos
+    }
+
+    // on the same line the gap before the repeated word is removed, otherwise
+    // the word and the spaces after it so the previous line stays untouched
+    fn removal(
+        &self,
+        last: &LastWord,
+        start: Position,
+        end: Position,
+        chars: &[char],
+        end_column: usize,
+    ) -> Fix {
+        if last.end.line == start.line {
+            return Fix {
+                start: last.end,
+                end,
+                replacement: String::new(),
+            };
+        }
+
+        let spaces = chars[end_column..]
+            .iter()
+            .take_while(|ch| ch.is_whitespace())
+            .count();
+
+        Fix {
+            start,
+            end: Position {
+                line: end.line,
+                column: end.column + spaces,
+            },
+            replacement: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::fix::apply_fixes;
+    use crate::spell_checker::repeated_words::RepeatedWords;
+    use crate::spell_checker::tokenizer::get_words_from_line;
+
+    fn fix_repeated(content: &str) -> (String, usize) {
+        let mut repeated_words = RepeatedWords::new();
+        let typos: Vec<_> = content
+            .lines()
+            .enumerate()
+            .flat_map(|(num, line)| {
+                repeated_words.check_line(line, &get_words_from_line(line), num + 1)
+            })
+            .collect();
+        let fixes: Vec<_> = typos.iter().filter_map(|typo| typo.fix.as_ref()).collect();
+
+        apply_fixes(content, &fixes)
+    }
+
+    #[test]
+    fn check_repeated_words() {
+        assert_eq!(
+            fix_repeated("it is is fine\n"),
+            ("it is fine\n".to_string(), 1)
+        );
+        assert_eq!(
+            fix_repeated("// end of the\n// the comment\n"),
+            ("// end of the\n// comment\n".to_string(), 1)
+        );
+        assert_eq!(
+            fix_repeated("the, the\nword\n\nword\n"),
+            ("the, the\nword\n\nword\n".to_string(), 0)
+        );
+        assert_eq!(
+            fix_repeated("- apple\n- apple\n> quote\n> quote\n"),
+            ("- apple\n- apple\n> quote\n> quote\n".to_string(), 0)
+        );
+    }
+}
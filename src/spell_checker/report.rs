@@ -0,0 +1,372 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use super::cache::typo_to_json;
+use super::dict::compare_words;
+use super::options::Options;
+use super::stats::CheckStats;
+use super::typo::{Typo, TypoKind};
+use super::wordlist::wordlist_entry;
+use super::Checker;
+
+pub const TEXT: &str = "text";
+pub const JSON: &str = "json";
+pub const GROUP_BY_WORD: &str = "word";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            TEXT => Some(Format::Text),
+            JSON => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+// typos are printed under a header per file unless they are grouped or
+// printed as json at the end
+pub fn prints_files(options: &Options) -> bool {
+    options.format == Format::Text && !options.group_by_word && !options.unique
+}
+
+// notes about files go to stderr when they would mix with a report that is
+// printed at the end
+pub fn note(options: &Options, message: &str) {
+    match prints_files(options) {
+        true => println!("{message}"),
+        false => eprintln!("{message}"),
+    }
+}
+
+// every occurrence of a word with the same kind of problem, a word written
+// in several casings is shown like a wordlist entry
+struct WordGroup<'a> {
+    word: String,
+    kind: &'a TypoKind,
+    locations: Vec<(&'a Path, &'a Typo)>,
+}
+
+impl WordGroup<'_> {
+    fn files(&self) -> usize {
+        let files: HashSet<&Path> = self.locations.iter().map(|(path, _)| *path).collect();
+        files.len()
+    }
+
+    fn suggestions(&self, checker: &Checker) -> Vec<String> {
+        match self.kind {
+            TypoKind::Misspelled | TypoKind::Forbidden => {
+                checker.suggest(&self.word, self.locations[0].0)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+// words are grouped ignoring case, most frequent words first
+fn group_by_word(stats: &CheckStats) -> Vec<WordGroup<'_>> {
+    let mut groups: Vec<WordGroup> = Vec::new();
+    let mut indexes: HashMap<(String, &str), usize> = HashMap::new();
+
+    for (path, typos) in stats.reported.iter() {
+        for typo in typos.iter() {
+            let key = (typo.word.to_lowercase(), typo.kind.name());
+            let index = *indexes.entry(key).or_insert_with(|| {
+                groups.push(WordGroup {
+                    word: typo.word.clone(),
+                    kind: &typo.kind,
+                    locations: Vec::new(),
+                });
+                groups.len() - 1
+            });
+            let group = &mut groups[index];
+            if group.word != typo.word {
+                group.word = wordlist_entry(&typo.word);
+            }
+            group.locations.push((path.as_path(), typo));
+        }
+    }
+
+    groups.sort_by(|a, b| {
+        b.locations
+            .len()
+            .cmp(&a.locations.len())
+            .then_with(|| compare_words(&a.word, &b.word))
+    });
+    groups
+}
+
+fn show_groups(stats: &CheckStats, checker: &Checker) {
+    for group in group_by_word(stats) {
+        print!(
+            "{}: {} time(s) in {} file(s)",
+            group.word,
+            group.locations.len(),
+            group.files()
+        );
+        let suggestions = group.suggestions(checker);
+        match group.kind {
+            TypoKind::Repeated(_) => println!(" (repeated word)"),
+            _ if !suggestions.is_empty() => {
+                println!("{} (did you mean {}?)", group.kind, suggestions.join(", "))
+            }
+            kind => println!("{kind}"),
+        }
+
+        for (path, typo) in group.locations.iter() {
+            match &typo.token {
+                Some(token) => println!(
+                    "    {}:{}:{} in {token}",
+                    path.display(),
+                    typo.line,
+                    typo.column
+                ),
+                None => println!("    {}:{}:{}", path.display(), typo.line, typo.column),
+            }
+        }
+    }
+    println!();
+}
+
+fn groups_to_json(stats: &CheckStats, checker: &Checker) -> Value {
+    let groups: Vec<Value> = group_by_word(stats)
+        .iter()
+        .map(|group| {
+            let locations: Vec<Value> = group
+                .locations
+                .iter()
+                .map(|(path, typo)| {
+                    json!({
+                        "path": path.display().to_string(),
+                        "line": typo.line,
+                        "column": typo.column,
+                        "token": typo.token,
+                    })
+                })
+                .collect();
+            json!({
+                "word": group.word,
+                "kind": group.kind.name(),
+                "count": group.locations.len(),
+                "files": group.files(),
+                "suggestions": group.suggestions(checker),
+                "locations": locations,
+            })
+        })
+        .collect();
+    Value::Array(groups)
+}
+
+// distinct misspelled words written like wordlist entries
+fn unique_words(stats: &CheckStats) -> Vec<String> {
+    let mut words: Vec<String> = stats
+        .reported
+        .iter()
+        .flat_map(|(_, typos)| typos.iter())
+        .filter(|typo| matches!(typo.kind, TypoKind::Misspelled))
+        .map(|typo| wordlist_entry(&typo.word))
+        .collect();
+    words.sort_by(|a, b| compare_words(a, b));
+    words.dedup();
+    words
+}
+
+fn show_counts<K: std::fmt::Display>(title: &str, counts: &[(K, u64)]) {
+    if counts.is_empty() {
+        return;
+    }
+    println!("->{title}:");
+    for (key, count) in counts.iter() {
+        println!("    {key}: {count}");
+    }
+}
+
+fn show_text(stats: &CheckStats, options: &Options) {
+    println!("===SUCCESSFULLY FINISHED===");
+    println!("->Files checked: {}", stats.files_checked);
+    println!("->Dirs checked: {}", stats.dirs_checked);
+    println!("->Binary files skipped: {}", stats.binary_files_skipped);
+    println!(
+        "->Files with invalid encoding: {}",
+        stats.invalid_encoding_files
+    );
+    println!("->Files from cache: {}", stats.cached_files);
+    println!("->Typos found: {}", stats.typos_num);
+    println!("->Casing errors: {}", stats.casing_errors);
+    println!("->Repeated words: {}", stats.repeated_words);
+    println!("->Style rule violations: {}", stats.style_violations);
+    println!("->Variant spellings: {}", stats.variant_mismatches);
+    println!("->Fixes applied: {}", stats.fixes_applied);
+    println!("->Errors: {}", stats.errors);
+
+    if options.stats {
+        let top = usize::from(options.top);
+        let show_paths = |counts: Vec<(&Path, u64)>| -> Vec<(String, u64)> {
+            counts
+                .into_iter()
+                .take(top)
+                .map(|(path, count)| (path.display().to_string(), count))
+                .collect()
+        };
+
+        println!("->Words checked: {}", stats.words_checked);
+        println!("->Lines checked: {}", stats.lines_checked);
+        for (reason, count) in stats.skipped_files.iter() {
+            println!("->Files skipped ({reason}): {count}");
+        }
+        println!(
+            "->Elapsed time: {:.2}s",
+            stats.started.elapsed().as_secs_f64()
+        );
+        show_counts("Problems by file", &show_paths(stats.problems_by_file()));
+        let extensions: Vec<(String, u64)> = stats
+            .problems_by_extension()
+            .into_iter()
+            .take(top)
+            .collect();
+        show_counts("Problems by extension", &extensions);
+        show_counts(
+            "Problems by directory",
+            &show_paths(stats.problems_by_directory()),
+        );
+        show_counts("Most frequent typos", &stats.top_words(top));
+    }
+    println!("===THANKS FOR USING THIS SOFTWARE!===");
+}
+
+fn counts_to_json<K: ToString>(counts: impl IntoIterator<Item = (K, u64)>) -> Value {
+    let counts: Vec<Value> = counts
+        .into_iter()
+        .map(|(key, count)| json!({ "name": key.to_string(), "count": count }))
+        .collect();
+    Value::Array(counts)
+}
+
+pub fn summary_json(stats: &CheckStats, options: &Options) -> Value {
+    let top = usize::from(options.top);
+    let skipped: Map<String, Value> = stats
+        .skipped_files
+        .iter()
+        .map(|(reason, count)| (reason.to_string(), json!(count)))
+        .collect();
+    let paths = |counts: Vec<(&Path, u64)>| -> Vec<(String, u64)> {
+        counts
+            .into_iter()
+            .map(|(path, count)| (path.display().to_string(), count))
+            .collect()
+    };
+
+    json!({
+        "files_checked": stats.files_checked,
+        "dirs_checked": stats.dirs_checked,
+        "binary_files_skipped": stats.binary_files_skipped,
+        "invalid_encoding_files": stats.invalid_encoding_files,
+        "cached_files": stats.cached_files,
+        "typos": stats.typos_num,
+        "casing_errors": stats.casing_errors,
+        "repeated_words": stats.repeated_words,
+        "style_violations": stats.style_violations,
+        "variant_spellings": stats.variant_mismatches,
+        "fixes_applied": stats.fixes_applied,
+        "errors": stats.errors,
+        "words_checked": stats.words_checked,
+        "lines_checked": stats.lines_checked,
+        "skipped_files": skipped,
+        "elapsed_ms": stats.started.elapsed().as_millis() as u64,
+        "problems_by_file": counts_to_json(paths(stats.problems_by_file())),
+        "problems_by_extension": counts_to_json(stats.problems_by_extension()),
+        "problems_by_directory": counts_to_json(paths(stats.problems_by_directory())),
+        "top_words": counts_to_json(stats.top_words(top)),
+    })
+}
+
+// the json report holds the typos of every file and the summary
+fn show_json(stats: &CheckStats, checker: &Checker) {
+    let options = checker.options;
+    let files: Vec<Value> = stats
+        .reported
+        .iter()
+        .filter(|(_, typos)| !typos.is_empty())
+        .map(|(path, typos)| {
+            json!({
+                "path": path.display().to_string(),
+                "typos": typos.iter().map(typo_to_json).collect::<Vec<Value>>(),
+            })
+        })
+        .collect();
+
+    let mut report = json!({ "files": files, "summary": summary_json(stats, options) });
+    if options.group_by_word {
+        report["words"] = groups_to_json(stats, checker);
+    }
+    println!("{report}");
+}
+
+// --unique prints nothing but the words so they can be piped
+pub fn show_result(stats: &CheckStats, checker: &Checker) {
+    let options = checker.options;
+
+    match (options.format, options.unique) {
+        (Format::Text, true) => {
+            for word in unique_words(stats) {
+                println!("{word}");
+            }
+        }
+        (Format::Json, true) => println!("{}", json!(unique_words(stats))),
+        (Format::Text, false) => {
+            if options.group_by_word {
+                show_groups(stats, checker);
+            }
+            show_text(stats, options);
+        }
+        (Format::Json, false) => show_json(stats, checker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::spell_checker::report::{group_by_word, unique_words};
+    use crate::spell_checker::stats::CheckStats;
+    use crate::spell_checker::typo::{Typo, TypoKind};
+
+    fn typo(word: &str, line: usize) -> Typo {
+        Typo {
+            word: word.to_string(),
+            token: None,
+            line,
+            column: 1,
+            kind: TypoKind::Misspelled,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn check_word_groups() {
+        let mut stats = CheckStats::new();
+        stats.reported = vec![
+            (
+                PathBuf::from("a.md"),
+                vec![typo("Teh", 1), typo("GitHub", 2)],
+            ),
+            (PathBuf::from("b.md"), vec![typo("teh", 3)]),
+        ];
+
+        let groups = group_by_word(&stats);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            (groups[0].word.as_str(), groups[0].locations.len()),
+            ("teh", 2)
+        );
+        assert_eq!(groups[0].files(), 2);
+        assert_eq!(unique_words(&stats), vec!["GitHub", "teh"]);
+    }
+}
@@ -1,8 +1,32 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::typo::{Typo, TypoKind};
+
 pub struct CheckStats {
     pub files_checked: u32,
     pub dirs_checked: u32,
+    pub binary_files_skipped: u32,
+    pub invalid_encoding_files: u32,
+    pub cached_files: u32,
     pub typos_num: u64,
+    pub casing_errors: u64,
+    pub repeated_words: u64,
+    pub style_violations: u64,
+    pub variant_mismatches: u64,
+    pub fixes_applied: u64,
     pub errors: u32,
+    pub words_checked: u64,
+    pub lines_checked: u64,
+    // files skipped by each rule, e.g. an excluded extension
+    pub skipped_files: BTreeMap<&'static str, u32>,
+    pub file_problems: BTreeMap<PathBuf, u64>,
+    // misspelled words as written with their number of occurrences
+    pub typo_words: HashMap<String, u64>,
+    // typos of every file when they aren't printed while checking
+    pub reported: Vec<(PathBuf, Vec<Typo>)>,
+    pub started: Instant,
 }
 
 impl Default for CheckStats {
@@ -11,13 +35,160 @@ impl Default for CheckStats {
     }
 }
 
+fn sorted_counts<K: Ord>(counts: impl IntoIterator<Item = (K, u64)>) -> Vec<(K, u64)> {
+    let mut counts: Vec<(K, u64)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
 impl CheckStats {
     pub fn new() -> Self {
         Self {
             files_checked: 0,
             dirs_checked: 0,
+            binary_files_skipped: 0,
+            invalid_encoding_files: 0,
+            cached_files: 0,
             typos_num: 0,
+            casing_errors: 0,
+            repeated_words: 0,
+            style_violations: 0,
+            variant_mismatches: 0,
+            fixes_applied: 0,
             errors: 0,
+            words_checked: 0,
+            lines_checked: 0,
+            skipped_files: BTreeMap::new(),
+            file_problems: BTreeMap::new(),
+            typo_words: HashMap::new(),
+            reported: Vec::new(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn problems(&self) -> u64 {
+        self.typos_num
+            + self.casing_errors
+            + self.repeated_words
+            + self.style_violations
+            + self.variant_mismatches
+    }
+
+    pub fn skip(&mut self, reason: &'static str) {
+        *self.skipped_files.entry(reason).or_default() += 1;
+    }
+
+    pub fn add_typos(&mut self, path: &Path, typos: &[Typo]) {
+        for typo in typos.iter() {
+            match typo.kind {
+                TypoKind::Misspelled | TypoKind::Forbidden => {
+                    self.typos_num += 1;
+                    *self.typo_words.entry(typo.word.clone()).or_default() += 1;
+                }
+                TypoKind::WrongCase(_) => self.casing_errors += 1,
+                TypoKind::Repeated(_) => self.repeated_words += 1,
+                TypoKind::Style { .. } => self.style_violations += 1,
+                TypoKind::Variant { .. } => self.variant_mismatches += 1,
+            }
+        }
+        if !typos.is_empty() {
+            *self.file_problems.entry(path.to_path_buf()).or_default() += typos.len() as u64;
         }
     }
+
+    pub fn top_words(&self, limit: usize) -> Vec<(&str, u64)> {
+        let counts = self
+            .typo_words
+            .iter()
+            .map(|(word, count)| (word.as_str(), *count));
+        sorted_counts(counts).into_iter().take(limit).collect()
+    }
+
+    pub fn problems_by_file(&self) -> Vec<(&Path, u64)> {
+        let counts = self
+            .file_problems
+            .iter()
+            .map(|(path, count)| (path.as_path(), *count));
+        sorted_counts(counts)
+    }
+
+    // files without an extension are counted under their whole name
+    pub fn problems_by_extension(&self) -> Vec<(String, u64)> {
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        for (path, count) in self.file_problems.iter() {
+            let extension = match path.extension() {
+                Some(extension) => extension.to_string_lossy().to_string(),
+                None => path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+            };
+            *counts.entry(extension).or_default() += count;
+        }
+        sorted_counts(counts)
+    }
+
+    pub fn problems_by_directory(&self) -> Vec<(&Path, u64)> {
+        let mut counts: BTreeMap<&Path, u64> = BTreeMap::new();
+        for (path, count) in self.file_problems.iter() {
+            let dir = path.parent().unwrap_or(Path::new(""));
+            *counts.entry(dir).or_default() += count;
+        }
+        sorted_counts(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::spell_checker::stats::CheckStats;
+    use crate::spell_checker::typo::{Position, Typo, TypoKind};
+
+    fn typo(word: &str, kind: TypoKind) -> Typo {
+        Typo {
+            word: word.to_string(),
+            token: None,
+            line: 1,
+            column: 1,
+            kind,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn check_grouped_stats() {
+        let mut stats = CheckStats::new();
+        stats.add_typos(
+            Path::new("src/main.rs"),
+            &[
+                typo("teh", TypoKind::Misspelled),
+                typo("wrold", TypoKind::Misspelled),
+            ],
+        );
+        stats.add_typos(
+            Path::new("docs/guide.md"),
+            &[
+                typo("teh", TypoKind::Misspelled),
+                typo("the", TypoKind::Repeated(Position { line: 1, column: 1 })),
+            ],
+        );
+        stats.add_typos(
+            Path::new("src/lib.rs"),
+            &[typo("teh", TypoKind::Misspelled)],
+        );
+
+        assert_eq!(stats.problems(), 5);
+        assert_eq!(stats.top_words(1), vec![("teh", 3)]);
+        assert_eq!(
+            stats.problems_by_extension(),
+            vec![("rs".to_string(), 3), ("md".to_string(), 2)]
+        );
+        assert_eq!(
+            stats.problems_by_directory(),
+            vec![(Path::new("src"), 3), (Path::new("docs"), 2)]
+        );
+        assert_eq!(stats.problems_by_file()[0], (Path::new("docs/guide.md"), 2));
+    }
 }
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::tokenizer::Token;
+use super::typo::{Fix, Position, Typo, TypoKind};
+use super::wordlist::strip_comment;
+
+pub const PREFERRED_TERM: &str = "preferred-term";
+pub const FORBIDDEN_TERM: &str = "forbidden-term";
+
+// "whitelist -> allowlist" asks for a preferred term, a line with only a
+// term forbids it. A rule id can follow in brackets like [inclusive-language]
+#[derive(Clone, PartialEq, Debug)]
+pub struct StyleRule {
+    pub id: String,
+    // terms with capitals only match the same casing, like brand names
+    pub term: String,
+    pub preferred: Option<String>,
+}
+
+#[derive(Default)]
+pub struct StyleRules {
+    rules: HashMap<String, Vec<StyleRule>>,
+}
+
+pub fn parse_rule(line: &str) -> Result<Option<StyleRule>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let mut line = strip_comment(line).trim_end();
+
+    let mut id = None;
+    if let Some(start) = line.find('[') {
+        let Some(name) = line[start + 1..].strip_suffix(']') else {
+            return Err("rule id should be closed with ]".to_string());
+        };
+        id = Some(name.trim().to_string());
+        line = line[..start].trim_end();
+    }
+
+    let (term, preferred) = match line.split_once("->") {
+        Some((term, preferred)) => (term.trim(), Some(preferred.trim())),
+        None => (line, None),
+    };
+    for text in [Some(term), preferred].into_iter().flatten() {
+        if text.is_empty() {
+            return Err("rule without a term".to_string());
+        }
+        if text.contains(char::is_whitespace) {
+            return Err(format!(
+                "{text} contains whitespace, rules match single words"
+            ));
+        }
+    }
+
+    let default_id = match preferred {
+        Some(_) => PREFERRED_TERM,
+        None => FORBIDDEN_TERM,
+    };
+    Ok(Some(StyleRule {
+        id: id
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| default_id.to_string()),
+        term: term.to_string(),
+        preferred: preferred.map(str::to_string),
+    }))
+}
+
+// a lowercase preferred term follows the casing of the replaced word
+pub fn match_case(word: &str, preferred: &str) -> String {
+    if preferred.chars().any(char::is_uppercase) {
+        return preferred.to_string();
+    }
+    if word.chars().count() > 1 && !word.chars().any(char::is_lowercase) {
+        return preferred.to_uppercase();
+    }
+
+    let mut chars = preferred.chars();
+    match (word.chars().next(), chars.next()) {
+        (Some(first), Some(preferred_first)) if first.is_uppercase() => {
+            preferred_first.to_uppercase().chain(chars).collect()
+        }
+        _ => preferred.to_string(),
+    }
+}
+
+impl StyleRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(&mut self, content: &str, path: &Path) -> Result<Vec<StyleRule>, String> {
+        let mut loaded = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            match parse_rule(line) {
+                Ok(Some(rule)) => {
+                    self.rules
+                        .entry(rule.term.to_lowercase())
+                        .or_default()
+                        .push(rule.clone());
+                    loaded.push(rule);
+                }
+                Ok(None) => {}
+                Err(error) => return Err(format!("{}:{}: {error}", path.display(), index + 1)),
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    pub fn find(&self, word: &str) -> Option<&StyleRule> {
+        self.rules.get(&word.to_lowercase())?.iter().find(|rule| {
+            let matches = match rule.term.chars().any(char::is_uppercase) {
+                true => rule.term == word,
+                false => true,
+            };
+            matches && rule.preferred.as_deref() != Some(word)
+        })
+    }
+
+    // rules are checked even for words found in the dictionaries, preferred
+    // terms are only fixable for whole words, renaming parts of identifiers
+    // is left to the user
+    pub fn check(&self, word: &Token, token: &Token, line_num: usize) -> Option<Typo> {
+        let rule = self.find(&word.text)?;
+        let start = Position {
+            line: line_num,
+            column: word.column + 1,
+        };
+        let end = Position {
+            line: line_num,
+            column: word.column + word.text.chars().count() + 1,
+        };
+        let preferred = rule
+            .preferred
+            .as_ref()
+            .map(|preferred| match_case(&word.text, preferred));
+
+        let whole_word = word.text == token.text;
+
+        Some(Typo {
+            word: word.text.clone(),
+            token: (!whole_word).then(|| token.text.clone()),
+            line: line_num,
+            column: start.column,
+            fix: preferred
+                .clone()
+                .filter(|_| whole_word)
+                .map(|replacement| Fix {
+                    start,
+                    end,
+                    replacement,
+                }),
+            kind: TypoKind::Style {
+                rule: rule.id.clone(),
+                preferred,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::spell_checker::style::{parse_rule, StyleRules};
+    use crate::spell_checker::tokenizer::Token;
+    use crate::spell_checker::typo::TypoKind;
+
+    #[test]
+    fn check_style_rules() {
+        let mut rules = StyleRules::new();
+        let content = "# terms\nwhitelist -> allowlist [inclusive-language]\ne-mail -> email\nGithub -> GitHub\nmaster\n";
+        assert_eq!(
+            rules.load(content, Path::new("rules.txt")).unwrap().len(),
+            4
+        );
+
+        let token = Token {
+            text: "Whitelist".to_string(),
+            column: 4,
+        };
+        let typo = rules.check(&token, &token, 2).unwrap();
+        assert!(
+            matches!(&typo.kind, TypoKind::Style { rule, preferred: Some(preferred) } if rule == "inclusive-language" && preferred == "Allowlist")
+        );
+        assert_eq!(typo.fix.unwrap().end.column, 14);
+
+        let identifier = Token {
+            text: "whitelistEntries".to_string(),
+            column: 4,
+        };
+        let word = Token {
+            text: "whitelist".to_string(),
+            column: 4,
+        };
+        let typo = rules.check(&word, &identifier, 2).unwrap();
+        assert!(typo.fix.is_none());
+        assert_eq!(typo.token.as_deref(), Some("whitelistEntries"));
+
+        assert_eq!(rules.find("MASTER").unwrap().id, "forbidden-term");
+        assert!(rules.find("GitHub").is_none());
+        assert!(rules.find("github").is_none());
+        assert!(rules.find("Github").is_some());
+        assert!(parse_rule("a -> b c").is_err());
+        assert!(parse_rule("-> b").is_err());
+    }
+}
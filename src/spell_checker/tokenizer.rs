@@ -0,0 +1,222 @@
+use super::parse_variables;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub text: String,
+    // index of the first char of the token in its line
+    pub column: usize,
+}
+
+fn is_char_valid(ch: &char) -> bool {
+    ch.is_alphanumeric() || *ch == '_'
+}
+
+// chars joining words into one compound token like my-crate-name,
+// serde.json or src/main.rs
+fn is_joiner(ch: &char) -> bool {
+    matches!(ch, '-' | '.' | '/')
+}
+
+// ascii and typographic apostrophes, kept inside words like don't
+fn is_apostrophe(ch: &char) -> bool {
+    matches!(ch, '\'' | '\u{2019}' | '\u{02BC}')
+}
+
+pub fn normalize_apostrophes(word: &str) -> String {
+    word.chars()
+        .map(|ch| if is_apostrophe(&ch) { '\'' } else { ch })
+        .collect()
+}
+
+// prefixes of string literals like f'text' in python or b'a' in rust
+fn is_string_prefix(word: &str) -> bool {
+    matches!(
+        word.to_lowercase().as_str(),
+        "f" | "b" | "u" | "r" | "rb" | "br" | "fr" | "rf"
+    )
+}
+
+fn joins_words(ch: &char, current_word: &str, next: Option<&char>) -> bool {
+    if is_joiner(ch) {
+        return !current_word.is_empty() && next.is_some_and(is_char_valid);
+    }
+
+    is_apostrophe(ch)
+        && current_word.ends_with(char::is_alphabetic)
+        && !is_string_prefix(current_word)
+        && next.is_some_and(|ch| ch.is_alphabetic())
+}
+
+fn push_token(tokens: &mut Vec<Token>, text: &mut String, column: usize) {
+    if text.chars().any(|ch| ch.is_alphabetic()) {
+        tokens.push(Token {
+            text: text.clone(),
+            column,
+        });
+    }
+    text.clear();
+}
+
+pub fn get_words_from_line(line: &str) -> Vec<Token> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut current_word = String::new();
+    let mut column = 0;
+
+    for (i, ch) in chars.iter().enumerate() {
+        if is_char_valid(ch) {
+            if current_word.is_empty() {
+                column = i;
+            }
+            current_word.push(*ch);
+        } else if joins_words(ch, &current_word, chars.get(i + 1)) {
+            current_word.push(*ch);
+        } else {
+            push_token(&mut tokens, &mut current_word, column);
+        }
+    }
+
+    push_token(&mut tokens, &mut current_word, column);
+
+    tokens
+}
+
+pub fn split_compound(token: &Token) -> Vec<Token> {
+    let mut parts: Vec<Token> = Vec::new();
+    let mut current_part = String::new();
+    let mut column = token.column;
+
+    for (i, ch) in token.text.chars().enumerate() {
+        if is_joiner(&ch) {
+            push_token(&mut parts, &mut current_part, column);
+        } else {
+            if current_part.is_empty() {
+                column = token.column + i;
+            }
+            current_part.push(ch);
+        }
+    }
+
+    push_token(&mut parts, &mut current_part, column);
+
+    parts
+}
+
+fn locate_words(token: &Token, words: Vec<String>) -> Vec<Token> {
+    let chars: Vec<char> = token.text.chars().collect();
+    let mut located: Vec<Token> = Vec::new();
+    let mut position = 0;
+
+    for word in words {
+        let word_chars: Vec<char> = word.chars().collect();
+        let offset = (position..chars.len())
+            .find(|&i| chars[i..].starts_with(&word_chars))
+            .unwrap_or(position);
+
+        position = offset + word_chars.len();
+        located.push(Token {
+            text: word,
+            column: token.column + offset,
+        });
+    }
+
+    located
+}
+
+pub fn split_identifier(token: &Token) -> Option<Vec<Token>> {
+    let parsing_functions = [
+        parse_variables::parse_snake_case,
+        parse_variables::parse_camel_case,
+        parse_variables::parse_alphanumeric,
+    ];
+
+    for parsing_func in parsing_functions {
+        if let Some(words) = parsing_func(&token.text) {
+            return Some(locate_words(token, words));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::tokenizer::{
+        get_words_from_line, split_compound, split_identifier, Token,
+    };
+
+    fn token(text: &str, column: usize) -> Token {
+        Token {
+            text: text.to_string(),
+            column,
+        }
+    }
+
+    #[test]
+    fn check_compound_tokens() {
+        assert_eq!(
+            get_words_from_line("use --exclude-path for my-crate-name."),
+            vec![
+                token("use", 0),
+                token("exclude-path", 6),
+                token("for", 19),
+                token("my-crate-name", 23)
+            ]
+        );
+        assert_eq!(
+            get_words_from_line("see serde.json and src/main.rs, 3.14"),
+            vec![
+                token("see", 0),
+                token("serde.json", 4),
+                token("and", 15),
+                token("src/main.rs", 19)
+            ]
+        );
+        assert_eq!(
+            split_compound(&token("my-crate-name", 10)),
+            vec![token("my", 10), token("crate", 13), token("name", 19)]
+        );
+    }
+
+    #[test]
+    fn check_apostrophes() {
+        assert_eq!(
+            get_words_from_line("don't isn\u{2019}t user's 'quoted' users'"),
+            vec![
+                token("don't", 0),
+                token("isn\u{2019}t", 6),
+                token("user's", 12),
+                token("quoted", 20),
+                token("users", 28)
+            ]
+        );
+        assert_eq!(
+            get_words_from_line("print(f'hello', b'a', u'text', rb'raw') I'm"),
+            vec![
+                token("print", 0),
+                token("f", 6),
+                token("hello", 8),
+                token("b", 16),
+                token("a", 18),
+                token("u", 22),
+                token("text", 24),
+                token("rb", 31),
+                token("raw", 34),
+                token("I'm", 40)
+            ]
+        );
+    }
+
+    #[test]
+    fn check_identifier_columns() {
+        assert_eq!(
+            split_identifier(&token("parseXMLDocument", 4)),
+            Some(vec![
+                token("parse", 4),
+                token("XML", 9),
+                token("Document", 12)
+            ])
+        );
+        assert_eq!(split_identifier(&token("word", 0)), None);
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use super::variants::Variant;
+
+// 1-based line and char column
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+// replaces the text from start up to, but not including, end
+#[derive(Clone)]
+pub struct Fix {
+    pub start: Position,
+    pub end: Position,
+    pub replacement: String,
+}
+
+#[derive(Clone)]
+pub enum TypoKind {
+    Misspelled,
+    WrongCase(String),
+    // the word is marked as forbidden in a wordlist
+    Forbidden,
+    // a style rule asks for another term or forbids the word
+    Style {
+        rule: String,
+        preferred: Option<String>,
+    },
+    // the word is spelled in the other English variant
+    Variant {
+        expected: Variant,
+        preferred: String,
+    },
+    // position of the first occurrence of the word
+    Repeated(Position),
+}
+
+impl TypoKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TypoKind::Misspelled => "misspelled",
+            TypoKind::WrongCase(_) => "wrong_case",
+            TypoKind::Forbidden => "forbidden",
+            TypoKind::Style { .. } => "style",
+            TypoKind::Variant { .. } => "variant",
+            TypoKind::Repeated(_) => "repeated",
+        }
+    }
+}
+
+// the note printed after a typo, empty for misspellings
+impl fmt::Display for TypoKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypoKind::Misspelled => Ok(()),
+            TypoKind::WrongCase(expected) => write!(f, " (should be written as {expected})"),
+            TypoKind::Forbidden => write!(f, " (forbidden word)"),
+            TypoKind::Style {
+                rule,
+                preferred: Some(preferred),
+            } => write!(f, " (use {preferred}, rule {rule})"),
+            TypoKind::Style {
+                rule,
+                preferred: None,
+            } => write!(f, " (forbidden by rule {rule})"),
+            TypoKind::Variant {
+                expected,
+                preferred,
+            } => write!(f, " ({expected} spelling is {preferred})"),
+            TypoKind::Repeated(first) => write!(
+                f,
+                " (repeated word, first at {}:{})",
+                first.line, first.column
+            ),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Typo {
+    pub word: String,
+    // compound token or identifier containing the word, if it differs
+    pub token: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub kind: TypoKind,
+    pub fix: Option<Fix>,
+}
+
+impl fmt::Display for Typo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "  * {}:{}", self.word, self.line)?;
+
+        if let Some(token) = &self.token {
+            write!(f, " in {token}")?;
+        }
+
+        write!(f, "{}", self.kind)
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::cache::is_cache_path;
+use super::file_utils::{get_files, is_binary};
+use super::options::{Options, STDIN_TARGET};
+use super::skip_file;
+use super::tokenizer::{get_words_from_line, split_compound, split_identifier};
+
+pub const US: &str = "us";
+pub const UK: &str = "uk";
+pub const AUTO: &str = "auto";
+
+const VARIANTS: &str = include_str!("../../dictionaries/variants.txt");
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Variant {
+    Us,
+    Uk,
+}
+
+impl Variant {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            US | "en-us" => Some(Variant::Us),
+            UK | "gb" | "en-gb" => Some(Variant::Uk),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Us => write!(f, "US"),
+            Variant::Uk => write!(f, "UK"),
+        }
+    }
+}
+
+// auto picks the variant most words of the targets are written in
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VariantMode {
+    Fixed(Variant),
+    Auto,
+}
+
+impl VariantMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            AUTO => Some(VariantMode::Auto),
+            _ => Variant::from_name(name).map(VariantMode::Fixed),
+        }
+    }
+}
+
+// words spelled differently in US and UK English, both spellings are usually
+// in the dictionaries so they are compared after the lookup
+pub struct Variants {
+    spellings: HashMap<String, (Variant, String)>,
+}
+
+impl Default for Variants {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Variants {
+    pub fn new() -> Self {
+        let mut spellings = HashMap::new();
+        let pairs = VARIANTS
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once(' '));
+
+        for (us, uk) in pairs {
+            spellings.insert(us.to_string(), (Variant::Us, uk.to_string()));
+            spellings.insert(uk.to_string(), (Variant::Uk, us.to_string()));
+        }
+
+        Self { spellings }
+    }
+
+    // spelling of the word in the other variant if the word isn't written in
+    // the expected one
+    pub fn other_spelling(&self, word: &str, expected: Variant) -> Option<&str> {
+        match self.spellings.get(&word.to_lowercase()) {
+            Some((variant, other)) if *variant != expected => Some(other),
+            _ => None,
+        }
+    }
+
+    pub fn count(&self, text: &str, counts: &mut VariantCounts) {
+        for line in text.lines() {
+            for token in get_words_from_line(line) {
+                for part in split_compound(&token) {
+                    let words = split_identifier(&part).unwrap_or_else(|| vec![part]);
+                    for word in words {
+                        match self.spellings.get(&word.text.to_lowercase()) {
+                            Some((Variant::Us, _)) => counts.us += 1,
+                            Some((Variant::Uk, _)) => counts.uk += 1,
+                            None => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct VariantCounts {
+    pub us: usize,
+    pub uk: usize,
+}
+
+impl VariantCounts {
+    // None without variant words or on a tie
+    pub fn majority(&self) -> Option<Variant> {
+        match self.us.cmp(&self.uk) {
+            std::cmp::Ordering::Greater => Some(Variant::Us),
+            std::cmp::Ordering::Less => Some(Variant::Uk),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+// variant used by most words in the target files, stdin isn't read twice
+pub fn detect_variant(options: &Options, variants: &Variants) -> Option<Variant> {
+    let mut counts = VariantCounts::default();
+    let mut count_file = |path: &Path| {
+        if is_cache_path(path) || skip_file(path, options).unwrap_or(true) {
+            return;
+        }
+        if let Ok(content) = fs::read(path) {
+            if !is_binary(&content) {
+                variants.count(&String::from_utf8_lossy(&content), &mut counts);
+            }
+        }
+    };
+
+    for target in &options.targets {
+        if target == Path::new(STDIN_TARGET) {
+            continue;
+        }
+        for file in get_files(target).into_iter().filter_map(|x| x.ok()) {
+            if file.path().is_file() {
+                count_file(file.path());
+            }
+        }
+    }
+
+    counts.majority()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spell_checker::variants::{Variant, VariantCounts, Variants};
+
+    #[test]
+    fn check_variant_detection() {
+        let variants = Variants::new();
+        assert_eq!(
+            variants.other_spelling("Colour", Variant::Us),
+            Some("color")
+        );
+        assert_eq!(variants.other_spelling("color", Variant::Us), None);
+        assert_eq!(variants.other_spelling("house", Variant::Uk), None);
+
+        let mut counts = VariantCounts::default();
+        variants.count(
+            "fn initialise_colours() {}\nlet behaviour = \"color\";\n// normalisedValue",
+            &mut counts,
+        );
+        assert_eq!((counts.us, counts.uk), (1, 4));
+        assert_eq!(counts.majority(), Some(Variant::Uk));
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs::canonicalize;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{recommended_watcher, Event, EventKind, RecursiveMode, Watcher};
+
+use super::cache::is_cache_path;
+use super::lsp::PROJECT_WORDLIST;
+use super::options::{Options, STDIN_TARGET};
+use super::report::{prints_files, show_result};
+use super::stats::CheckStats;
+use super::{check_targets, handle_correctness_check, load_diff, skip_file, Checker};
+
+// editors often write a file in several steps, events arriving within this
+// time are handled together
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+fn canonical_paths<'a>(paths: impl Iterator<Item = &'a PathBuf>) -> Vec<PathBuf> {
+    paths
+        .filter(|path| path.as_path() != Path::new(STDIN_TARGET))
+        .filter_map(|path| canonicalize(path).ok())
+        .collect()
+}
+
+// only the directory is canonicalized, so files that don't exist yet, like
+// a project wordlist created later, get the same path as in events
+fn file_path(path: &Path) -> Option<PathBuf> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    Some(canonicalize(dir).ok()?.join(path.file_name()?))
+}
+
+fn collect_paths(event: notify::Result<Event>, changed: &mut BTreeSet<PathBuf>) {
+    let Ok(event) = event else {
+        return;
+    };
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return;
+    }
+
+    for path in event.paths {
+        let in_git_dir = path
+            .components()
+            .any(|component| component.as_os_str() == ".git");
+        if in_git_dir || is_cache_path(&path) {
+            continue;
+        }
+        if let Some(path) = file_path(&path) {
+            changed.insert(path);
+        }
+    }
+}
+
+fn reloads_wordlists(changed: &BTreeSet<PathBuf>, wordlists: &[PathBuf]) -> bool {
+    changed.iter().any(|path| wordlists.contains(path))
+}
+
+fn full_check(options: &Options, checker: &Checker) -> io::Result<CheckStats> {
+    let mut stats = CheckStats::new();
+    let diff = load_diff(options);
+    check_targets(options, checker, diff.as_ref(), &mut stats)?;
+    show_result(&stats, checker);
+
+    Ok(stats)
+}
+
+// checks all targets once, then re-checks files changed under the targets.
+// A changed wordlist, ignore list, style rules or project wordlist reloads them and checks everything again
+pub fn watch<'a>(options: &'a Options, mut checker: Checker<'a>) -> io::Result<CheckStats> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = recommended_watcher(sender).map_err(io::Error::other)?;
+
+    let targets = canonical_paths(options.targets.iter());
+    let project_wordlist = PathBuf::from(PROJECT_WORDLIST);
+    let wordlists: Vec<PathBuf> = options
+        .wordlists
+        .iter()
+        .map(|wordlist| &wordlist.path)
+        .chain(options.ignore.iter())
+        .chain(options.style_rules.iter())
+        .chain([&project_wordlist])
+        .filter_map(|path| file_path(path))
+        .collect();
+
+    // files are watched through their directory, editors replace files
+    // instead of writing them in place and wordlists may not exist yet
+    let mut watched: HashSet<PathBuf> = HashSet::new();
+    for path in targets.iter().chain(wordlists.iter()) {
+        let (dir, mode) = match path.is_dir() {
+            true => (path.as_path(), RecursiveMode::Recursive),
+            false => (
+                path.parent().unwrap_or(Path::new("/")),
+                RecursiveMode::NonRecursive,
+            ),
+        };
+        if watched.insert(dir.to_path_buf()) {
+            watcher.watch(dir, mode).map_err(io::Error::other)?;
+        }
+    }
+
+    // changed files are shown relative to the working directory like targets
+    let current_dir = std::env::current_dir().and_then(canonicalize).ok();
+
+    let mut stats = full_check(options, &checker)?;
+    println!("Watching for changes, press Ctrl-C to stop");
+
+    while let Ok(event) = receiver.recv() {
+        let mut changed: BTreeSet<PathBuf> = BTreeSet::new();
+        collect_paths(event, &mut changed);
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            collect_paths(event, &mut changed);
+        }
+
+        if reloads_wordlists(&changed, &wordlists) {
+            match Checker::new(options) {
+                Ok(reloaded) => {
+                    checker = reloaded;
+                    println!("\nWordlists changed, checking everything again");
+                    stats = full_check(options, &checker)?;
+                }
+                Err(error) => println!("{error}"),
+            }
+            continue;
+        }
+
+        let files: Vec<&PathBuf> = changed
+            .iter()
+            .filter(|path| path.is_file())
+            .filter(|path| targets.iter().any(|target| path.starts_with(target)))
+            .filter(|path| !skip_file(path, options).unwrap_or(true))
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+
+        let text = prints_files(options);
+        if text {
+            println!();
+        }
+        stats = CheckStats::new();
+        let diff = load_diff(options);
+        for file in files {
+            let shown = current_dir
+                .as_ref()
+                .and_then(|dir| file.strip_prefix(dir).ok());
+            if text {
+                println!("-> {}", shown.unwrap_or(file).display());
+            }
+            handle_correctness_check(file, &checker, diff.as_ref(), &mut stats);
+            if text {
+                println!();
+            }
+        }
+        show_result(&stats, &checker);
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+    use notify::{Event, EventKind};
+
+    use crate::spell_checker::watch::{collect_paths, file_path, reloads_wordlists};
+
+    #[test]
+    fn check_changed_paths() {
+        let dir = std::env::temp_dir().join(format!("rscheck-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join("notes.md"), "text").unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        let event = |kind: EventKind, name: &str| Ok(Event::new(kind).add_path(dir.join(name)));
+        let mut changed = BTreeSet::new();
+        collect_paths(
+            event(EventKind::Create(CreateKind::File), "notes.md"),
+            &mut changed,
+        );
+        collect_paths(
+            event(EventKind::Modify(ModifyKind::Any), ".git/index"),
+            &mut changed,
+        );
+        collect_paths(
+            event(EventKind::Remove(RemoveKind::File), "old.md"),
+            &mut changed,
+        );
+        collect_paths(
+            event(EventKind::Modify(ModifyKind::Any), ".rscheck-cache/results"),
+            &mut changed,
+        );
+        assert_eq!(changed, BTreeSet::from([dir.join("notes.md")]));
+
+        // a wordlist missing at startup still reloads once it's created
+        let wordlist = file_path(&dir.join("words.txt")).unwrap();
+        let wordlists = vec![wordlist.clone()];
+        assert!(!reloads_wordlists(&changed, &wordlists));
+        fs::write(&wordlist, "word\n").unwrap();
+        collect_paths(
+            event(EventKind::Create(CreateKind::File), "words.txt"),
+            &mut changed,
+        );
+        assert!(reloads_wordlists(&changed, &wordlists));
+        assert!(!reloads_wordlists(&changed, &[PathBuf::from("words.txt")]));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::Path;
+
+// wordlists have one entry per line, lines are trimmed and blank lines or
+// lines starting with # are skipped. Entries can be followed by flags in
+// brackets and a comment, like "colour [forbidden] # use color"
+pub const CASE: &str = "case";
+pub const NOCASE: &str = "nocase";
+pub const FORBIDDEN: &str = "forbidden";
+pub const NOSUGGEST: &str = "nosuggest";
+
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct EntryFlags {
+    // None keeps the default, entries with capitals are case sensitive
+    pub case_sensitive: Option<bool>,
+    pub forbidden: bool,
+    pub no_suggest: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Entry {
+    pub word: String,
+    pub flags: EntryFlags,
+}
+
+impl Entry {
+    pub fn new(word: &str) -> Self {
+        Self {
+            word: word.to_string(),
+            flags: EntryFlags::default(),
+        }
+    }
+}
+
+pub fn strip_comment(line: &str) -> &str {
+    match line.find(" #").or_else(|| line.find("\t#")) {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+fn parse_flags(flags: &str) -> Result<EntryFlags, String> {
+    let mut parsed = EntryFlags::default();
+
+    for flag in flags
+        .split(',')
+        .map(str::trim)
+        .filter(|flag| !flag.is_empty())
+    {
+        match flag {
+            CASE | NOCASE => {
+                if parsed.case_sensitive.is_some() {
+                    return Err("case and nocase can't be used together".to_string());
+                }
+                parsed.case_sensitive = Some(flag == CASE);
+            }
+            FORBIDDEN => parsed.forbidden = true,
+            NOSUGGEST => parsed.no_suggest = true,
+            _ => return Err(format!("unknown flag {flag}")),
+        }
+    }
+
+    Ok(parsed)
+}
+
+// None for blank and comment lines
+pub fn parse_line(line: &str) -> Result<Option<Entry>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let line = strip_comment(line).trim_end();
+
+    let (word, flags) = match line.find('[') {
+        Some(start) => {
+            let Some(flags) = line[start + 1..].strip_suffix(']') else {
+                return Err("flags should be closed with ]".to_string());
+            };
+            (line[..start].trim_end(), parse_flags(flags)?)
+        }
+        None => (line, EntryFlags::default()),
+    };
+
+    if word.is_empty() {
+        return Err("flags without a word".to_string());
+    }
+    if word.contains(char::is_whitespace) {
+        return Err(format!(
+            "{word} contains whitespace, use one entry per line"
+        ));
+    }
+
+    Ok(Some(Entry {
+        word: word.to_string(),
+        flags,
+    }))
+}
+
+// words with capitals after the first letter keep their casing, other
+// words match any casing
+pub fn wordlist_entry(word: &str) -> String {
+    if word.chars().skip(1).any(char::is_uppercase) {
+        word.to_string()
+    } else {
+        word.to_lowercase()
+    }
+}
+
+pub fn parse_wordlist(content: &str, path: &Path) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        match parse_line(line) {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => {}
+            Err(error) => return Err(format!("{}:{}: {error}", path.display(), index + 1)),
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::spell_checker::wordlist::{parse_line, parse_wordlist, Entry, EntryFlags};
+
+    #[test]
+    fn check_wordlist_syntax() {
+        let content =
+            "# header\r\n\r\n  serde  \r\nnpm [case]\nteh [forbidden, nosuggest] # typo\n";
+        let entries = parse_wordlist(content, Path::new("words.txt")).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                Entry::new("serde"),
+                Entry {
+                    word: "npm".to_string(),
+                    flags: EntryFlags {
+                        case_sensitive: Some(true),
+                        ..EntryFlags::default()
+                    },
+                },
+                Entry {
+                    word: "teh".to_string(),
+                    flags: EntryFlags {
+                        case_sensitive: None,
+                        forbidden: true,
+                        no_suggest: true,
+                    },
+                },
+            ]
+        );
+
+        assert!(parse_line("word [shout]").is_err());
+        assert!(parse_line("word [case, nocase]").is_err());
+        assert!(parse_line("word [case").is_err());
+        assert_eq!(
+            parse_wordlist("ok\ntwo words\n", Path::new("words.txt")),
+            Err("words.txt:2: two words contains whitespace, use one entry per line".to_string())
+        );
+    }
+}
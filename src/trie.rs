@@ -9,6 +9,47 @@ pub struct Trie {
     root: TrieNode,
 }
 
+// depth first search over the trie keeping one edit distance row per node,
+// adjacent transpositions count as a single edit
+struct SuggestSearch<'a> {
+    word: &'a [char],
+    max_distance: usize,
+    prefix: String,
+    results: Vec<(String, usize)>,
+}
+
+impl SuggestSearch<'_> {
+    fn visit(&mut self, node: &TrieNode, ch: char, prev_ch: Option<char>, rows: [&[usize]; 2]) {
+        let [prev_prev_row, prev_row] = rows;
+        let mut row = vec![prev_row[0] + 1; self.word.len() + 1];
+
+        for i in 1..=self.word.len() {
+            let cost = usize::from(self.word[i - 1] != ch);
+            row[i] = (row[i - 1] + 1)
+                .min(prev_row[i] + 1)
+                .min(prev_row[i - 1] + cost);
+
+            if i > 1 && Some(self.word[i - 1]) == prev_ch && self.word[i - 2] == ch {
+                row[i] = row[i].min(prev_prev_row[i - 2] + 1);
+            }
+        }
+
+        self.prefix.push(ch);
+        let distance = row[self.word.len()];
+        if node.end && distance <= self.max_distance {
+            self.results.push((self.prefix.clone(), distance));
+        }
+
+        let min = |row: &[usize]| row.iter().copied().min().unwrap_or(0);
+        if min(&row) <= self.max_distance || min(prev_row) < self.max_distance {
+            for (next, child) in node.children.iter() {
+                self.visit(child, *next, Some(ch), [prev_row, &row]);
+            }
+        }
+        self.prefix.pop();
+    }
+}
+
 impl Default for Trie {
     fn default() -> Self {
         Trie::new()
@@ -50,4 +91,45 @@ impl Trie {
         }
         node.end
     }
+
+    // words within max_distance edits of the word with their distances
+    pub fn suggest(&self, word: &str, max_distance: usize) -> Vec<(String, usize)> {
+        let word: Vec<char> = word.chars().collect();
+        let first_row: Vec<usize> = (0..=word.len()).collect();
+        let mut search = SuggestSearch {
+            word: &word,
+            max_distance,
+            prefix: String::new(),
+            results: Vec::new(),
+        };
+
+        for (ch, child) in self.root.children.iter() {
+            search.visit(child, *ch, None, [&first_row, &first_row]);
+        }
+
+        search.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trie::Trie;
+
+    #[test]
+    fn check_suggestions() {
+        let mut trie = Trie::new();
+        for word in ["the", "then", "world", "would", "word"] {
+            trie.insert(word);
+        }
+
+        let mut suggestions = trie.suggest("wrold", 1);
+        suggestions.sort();
+        assert_eq!(suggestions, vec![("world".to_string(), 1)]);
+
+        let mut suggestions = trie.suggest("teh", 1);
+        suggestions.sort();
+        assert_eq!(suggestions, vec![("the".to_string(), 1)]);
+
+        assert!(trie.suggest("xyzzy", 2).is_empty());
+    }
 }